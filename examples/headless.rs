@@ -0,0 +1,15 @@
+use std::error::Error;
+
+use kitsune_ui::{headless::HeadlessRenderer, view::PhysicalSize, widget::Widget};
+
+#[tokio::main]
+async fn main() -> Result<(), Box<dyn Error>> {
+	let mut widget = String::from("Hello").bordered(10);
+
+	let renderer = HeadlessRenderer::new(true).await?;
+	let png = renderer.render_png(&mut widget, PhysicalSize::new(400, 200))?;
+
+	std::fs::write("headless.png", png)?;
+
+	Ok(())
+}
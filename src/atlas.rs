@@ -0,0 +1,394 @@
+use std::{collections::HashMap, hash::Hash, rc::Rc};
+
+use crate::{
+	text::{Font, Glyph, GlyphKey},
+	texture::Texture,
+	view::{PhysicalPosition, PhysicalSize},
+};
+
+const ATLAS_SIZE: u32 = 1024;
+
+/// The empty pixels to the right and below every glyph, so glyphs don't bleed into each other
+/// when they are sampled between pixels.
+const PADDING: u32 = 1;
+
+/// A texture that every glyph is rasterized into once, and then drawn from by every character
+/// that shows it.
+pub struct GlyphAtlas {
+	texture: Texture,
+	bind_group: Rc<wgpu::BindGroup>,
+	packer: Packer<GlyphKey>,
+	format: wgpu::TextureFormat,
+	full: bool,
+	growths: u64,
+}
+
+impl GlyphAtlas {
+	pub fn new(
+		device: &wgpu::Device,
+		format: wgpu::TextureFormat,
+		sampler: &wgpu::Sampler,
+		layout: &wgpu::BindGroupLayout,
+	) -> Self {
+		let size = wgpu::Extent3d {
+			width: ATLAS_SIZE,
+			height: ATLAS_SIZE,
+			depth_or_array_layers: 1,
+		};
+		let texture = Texture::new(device, size, format);
+		let bind_group = Rc::new(texture.bind_group(device, layout, sampler));
+
+		Self {
+			texture,
+			bind_group,
+			packer: Packer::new(ATLAS_SIZE),
+			format,
+			full: false,
+			growths: 0,
+		}
+	}
+
+	pub fn bind_group(&self) -> &Rc<wgpu::BindGroup> {
+		&self.bind_group
+	}
+
+	/// Start a new frame, the glyphs used in the previous one can be evicted from now on.
+	pub fn next_frame(&mut self) {
+		self.packer.next_frame();
+		self.full = false;
+	}
+
+	/// How many glyphs have been evicted so far, renderables that hold on to the place of a
+	/// glyph have to be recreated when this changes.
+	pub fn evictions(&self) -> u64 {
+		self.packer.evictions() + self.growths
+	}
+
+	/// Whether a glyph was left out in this frame because there was no room for it.
+	pub fn is_full(&self) -> bool {
+		self.full
+	}
+
+	/// Double the size of the texture, keeping every glyph that is in it, as long as the device
+	/// supports textures that large.
+	pub fn grow(
+		&mut self,
+		device: &wgpu::Device,
+		queue: &wgpu::Queue,
+		sampler: &wgpu::Sampler,
+		layout: &wgpu::BindGroupLayout,
+	) -> bool {
+		let size = self.packer.size() * 2;
+		let limit = device
+			.limits()
+			.max_texture_dimension_2d;
+		if size > limit {
+			return false;
+		}
+
+		let extent = wgpu::Extent3d {
+			width: size,
+			height: size,
+			depth_or_array_layers: 1,
+		};
+		let texture = Texture::new(device, extent, self.format);
+
+		// The copy is submitted right away, so it happens after the glyphs that were written to
+		// the old texture and before those that are written to the new one.
+		let mut encoder = device.create_command_encoder(&Default::default());
+		self.texture
+			.copy_to(&mut encoder, &texture);
+		queue.submit(Some(encoder.finish()));
+
+		self.bind_group = Rc::new(texture.bind_group(device, layout, sampler));
+		self.texture = texture;
+		self.packer.grow(size);
+		self.full = false;
+		self.growths += 1;
+		true
+	}
+
+	/// Where `glyph` is in the atlas, it is rasterized and written to the texture with `queue`
+	/// when it isn't there yet.
+	pub fn glyph(&mut self, font: &Font, glyph: &Glyph, queue: &wgpu::Queue) -> Option<AtlasGlyph> {
+		let key = font.key(glyph);
+		if let Some((position, size)) = self.packer.get(&key) {
+			return Some(AtlasGlyph::new(position, size, self.packer.size()));
+		}
+
+		let (size, data) = font.rasterize(glyph)?;
+		let padded = PhysicalSize::new(size.width + PADDING, size.height + PADDING);
+		let Some(position) = self.packer.insert(key, padded) else {
+			self.full = true;
+			return None;
+		};
+
+		// The padding is written as well, to clear whatever was there before.
+		let row = 4 * size.width as usize;
+		let mut pixels = vec![0; 4 * (padded.width * padded.height) as usize];
+		for (y, line) in data.chunks_exact(row).enumerate() {
+			let start = y * 4 * padded.width as usize;
+			pixels[start..start + row].copy_from_slice(line);
+		}
+		self.texture
+			.write_region(queue, position, padded, &pixels);
+
+		Some(AtlasGlyph::new(position, padded, self.packer.size()))
+	}
+}
+
+/// The place of a glyph in a `GlyphAtlas`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AtlasGlyph {
+	position: PhysicalPosition<u32>,
+	size: PhysicalSize<u32>,
+	atlas: u32,
+}
+
+impl AtlasGlyph {
+	fn new(position: PhysicalPosition<u32>, padded: PhysicalSize<u32>, atlas: u32) -> Self {
+		Self {
+			position,
+			size: PhysicalSize::new(padded.width - PADDING, padded.height - PADDING),
+			atlas,
+		}
+	}
+
+	pub fn size(&self) -> PhysicalSize<u32> {
+		self.size
+	}
+
+	/// The texture coordinate in the atlas of `uv` in the glyph, where `[0.0, 0.0]` is its
+	/// top-left and `[1.0, 1.0]` its bottom-right.
+	pub fn uv(&self, uv: [f32; 2]) -> [f32; 2] {
+		[
+			(self.position.x as f32 + uv[0] * self.size.width as f32) / self.atlas as f32,
+			(self.position.y as f32 + uv[1] * self.size.height as f32) / self.atlas as f32,
+		]
+	}
+}
+
+/// Packs rectangles into a square, in rows of rectangles that are at most as high as their row.
+#[derive(Debug)]
+pub struct Packer<K> {
+	size: u32,
+	shelves: Vec<Shelf<K>>,
+	entries: HashMap<K, Entry>,
+	frame: u64,
+	evictions: u64,
+}
+
+#[derive(Debug)]
+struct Shelf<K> {
+	y: u32,
+	height: u32,
+	slots: Vec<Slot<K>>,
+}
+
+#[derive(Debug)]
+struct Slot<K> {
+	x: u32,
+	width: u32,
+	key: Option<K>,
+}
+
+#[derive(Debug)]
+struct Entry {
+	shelf: usize,
+	position: PhysicalPosition<u32>,
+	size: PhysicalSize<u32>,
+	last_used: u64,
+}
+
+impl<K> Packer<K>
+where
+	K: Clone + Eq + Hash,
+{
+	pub fn new(size: u32) -> Self {
+		Self {
+			size,
+			shelves: vec![],
+			entries: HashMap::new(),
+			frame: 0,
+			evictions: 0,
+		}
+	}
+
+	pub fn next_frame(&mut self) {
+		self.frame += 1;
+	}
+
+	pub fn size(&self) -> u32 {
+		self.size
+	}
+
+	/// Make the square `size` pixels wide and high, keeping every rectangle where it is.
+	pub fn grow(&mut self, size: u32) {
+		if size <= self.size {
+			return;
+		}
+
+		for shelf in &mut self.shelves {
+			match shelf.slots.last_mut() {
+				Some(last) if last.key.is_none() => last.width += size - self.size,
+				_ => shelf.slots.push(Slot {
+					x: self.size,
+					width: size - self.size,
+					key: None,
+				}),
+			}
+		}
+		self.size = size;
+	}
+
+	/// How many rectangles have been removed to make room for others.
+	pub fn evictions(&self) -> u64 {
+		self.evictions
+	}
+
+	pub fn contains(&self, key: &K) -> bool {
+		self.entries.contains_key(key)
+	}
+
+	/// Where the rectangle of `key` is and how large it is, which marks it as used in this frame.
+	pub fn get(&mut self, key: &K) -> Option<(PhysicalPosition<u32>, PhysicalSize<u32>)> {
+		let entry = self.entries.get_mut(key)?;
+		entry.last_used = self.frame;
+		Some((entry.position, entry.size))
+	}
+
+	/// Find room for a rectangle of `size` and return its top-left, evicting other rectangles
+	/// when needed.
+	pub fn insert(&mut self, key: K, size: PhysicalSize<u32>) -> Option<PhysicalPosition<u32>> {
+		if size.width > self.size || size.height > self.size {
+			return None;
+		}
+		self.remove(&key);
+
+		let (shelf, slot) = loop {
+			match self.place(size) {
+				Some(place) => break place,
+				None if self.evict() => {}
+				None => return None,
+			}
+		};
+
+		let y = self.shelves[shelf].y;
+		let slots = &mut self.shelves[shelf].slots;
+		let free = &mut slots[slot];
+		let position = PhysicalPosition::new(free.x, y);
+		if free.width > size.width {
+			let rest = Slot {
+				x: free.x + size.width,
+				width: free.width - size.width,
+				key: None,
+			};
+			free.width = size.width;
+			slots.insert(slot + 1, rest);
+		}
+		slots[slot].key = Some(key.clone());
+
+		self.entries.insert(
+			key,
+			Entry {
+				shelf,
+				position,
+				size,
+				last_used: self.frame,
+			},
+		);
+		Some(position)
+	}
+
+	fn place(&mut self, size: PhysicalSize<u32>) -> Option<(usize, usize)> {
+		let fitting = self
+			.shelves
+			.iter()
+			.enumerate()
+			.filter(|(_, shelf)| shelf.height >= size.height)
+			.filter_map(|(index, shelf)| {
+				let slot = shelf
+					.slots
+					.iter()
+					.position(|x| x.key.is_none() && x.width >= size.width)?;
+				Some((shelf.height, index, slot))
+			})
+			.min();
+		if let Some((_, shelf, slot)) = fitting {
+			return Some((shelf, slot));
+		}
+
+		let y = self
+			.shelves
+			.last()
+			.map_or(0, |x| x.y + x.height);
+		if y + size.height > self.size {
+			return None;
+		}
+		self.shelves.push(Shelf {
+			y,
+			height: size.height,
+			slots: vec![Slot {
+				x: 0,
+				width: self.size,
+				key: None,
+			}],
+		});
+		Some((self.shelves.len() - 1, 0))
+	}
+
+	fn evict(&mut self) -> bool {
+		let oldest = self
+			.entries
+			.iter()
+			.filter(|(_, entry)| entry.last_used < self.frame)
+			.min_by_key(|(_, entry)| entry.last_used)
+			.map(|(key, _)| key.clone());
+
+		match oldest {
+			Some(key) => {
+				self.remove(&key);
+				self.evictions += 1;
+				true
+			}
+			None => false,
+		}
+	}
+
+	fn remove(&mut self, key: &K) {
+		let Some(entry) = self.entries.remove(key) else {
+			return;
+		};
+
+		let slots = &mut self.shelves[entry.shelf].slots;
+		let Some(mut index) = slots
+			.iter()
+			.position(|x| x.x == entry.position.x)
+		else {
+			return;
+		};
+		slots[index].key = None;
+
+		if slots
+			.get(index + 1)
+			.is_some_and(|x| x.key.is_none())
+		{
+			let next = slots.remove(index + 1);
+			slots[index].width += next.width;
+		}
+		if index > 0 && slots[index - 1].key.is_none() {
+			let slot = slots.remove(index);
+			index -= 1;
+			slots[index].width += slot.width;
+		}
+
+		// Empty shelves at the bottom are removed, so their space can be used by higher ones.
+		while self
+			.shelves
+			.last()
+			.is_some_and(|x| x.slots.len() == 1 && x.slots[0].key.is_none())
+		{
+			self.shelves.pop();
+		}
+	}
+}
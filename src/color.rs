@@ -0,0 +1,149 @@
+use std::str::FromStr;
+
+use thiserror::Error;
+
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum ParseColorError {
+	#[error("A hex color has 3, 4, 6 or 8 digits, but `{0}` has {1}")]
+	InvalidLength(String, usize),
+
+	#[error("`{0}` is not a hexadecimal number")]
+	InvalidDigit(String),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Color {
+	red: f32,
+	green: f32,
+	blue: f32,
+	alpha: f32,
+}
+
+impl Color {
+	pub const BLACK: Self = Self::linear(0.0, 0.0, 0.0, 1.0);
+	pub const WHITE: Self = Self::linear(1.0, 1.0, 1.0, 1.0);
+	pub const TRANSPARENT: Self = Self::linear(0.0, 0.0, 0.0, 0.0);
+
+	/// An opaque color from 8 bit srgb channels.
+	pub fn rgb(red: u8, green: u8, blue: u8) -> Self {
+		Self::rgba(red, green, blue, 255)
+	}
+
+	/// A color from 8 bit srgb channels, and an alpha where 255 is opaque.
+	pub fn rgba(red: u8, green: u8, blue: u8, alpha: u8) -> Self {
+		let channel = |x: u8| {
+			let x = x as f32 / 255.0;
+			if x <= 0.04045 {
+				x / 12.92
+			} else {
+				((x + 0.055) / 1.055).powf(2.4)
+			}
+		};
+		Self::linear(
+			channel(red),
+			channel(green),
+			channel(blue),
+			alpha as f32 / 255.0,
+		)
+	}
+
+	/// A color from channels in linear space, between `0.0` and `1.0`.
+	pub const fn linear(red: f32, green: f32, blue: f32, alpha: f32) -> Self {
+		Self {
+			red,
+			green,
+			blue,
+			alpha,
+		}
+	}
+
+	/// Parse a srgb hex code like `#ff8800`, the `#` is optional, the alpha can be given by a
+	/// fourth pair of digits, and every digit may be given once, as in `#f80`.
+	pub fn hex(value: &str) -> Result<Self, ParseColorError> {
+		let digits = value
+			.strip_prefix('#')
+			.unwrap_or(value);
+		let nibbles = digits
+			.chars()
+			.map(|x| x.to_digit(16).map(|x| x as u8))
+			.collect::<Option<Vec<_>>>()
+			.ok_or_else(|| ParseColorError::InvalidDigit(value.to_string()))?;
+
+		let channels = match nibbles.len() {
+			3 | 4 => nibbles
+				.iter()
+				.map(|x| x * 17)
+				.collect::<Vec<_>>(),
+			6 | 8 => nibbles
+				.chunks(2)
+				.map(|x| x[0] * 16 + x[1])
+				.collect(),
+			length => return Err(ParseColorError::InvalidLength(value.to_string(), length)),
+		};
+
+		Ok(Self::rgba(
+			channels[0],
+			channels[1],
+			channels[2],
+			channels
+				.get(3)
+				.copied()
+				.unwrap_or(255),
+		))
+	}
+
+	/// This color with its alpha replaced, where `1.0` is opaque.
+	pub fn with_alpha(self, alpha: f32) -> Self {
+		Self { alpha, ..self }
+	}
+
+	pub fn alpha(&self) -> f32 {
+		self.alpha
+	}
+
+	/// The red, green, blue and alpha channels in linear space.
+	pub fn to_linear(self) -> [f32; 4] {
+		[self.red, self.green, self.blue, self.alpha]
+	}
+
+	/// The red, green, blue and alpha channels as 8 bit srgb.
+	pub fn to_srgb(self) -> [u8; 4] {
+		let channel = |x: f32| {
+			let x = x.clamp(0.0, 1.0);
+			let x = if x <= 0.0031308 {
+				x * 12.92
+			} else {
+				1.055 * x.powf(1.0 / 2.4) - 0.055
+			};
+			(x * 255.0).round() as u8
+		};
+		[
+			channel(self.red),
+			channel(self.green),
+			channel(self.blue),
+			(self.alpha.clamp(0.0, 1.0) * 255.0).round() as u8,
+		]
+	}
+
+	/// The color between this one at `0.0` and `other` at `1.0`, blended in linear space.
+	pub fn mix(self, other: Self, amount: f32) -> Self {
+		let (a, b) = (self.to_linear(), other.to_linear());
+		let [red, green, blue, alpha] = std::array::from_fn(|i| a[i] + (b[i] - a[i]) * amount);
+		Self::linear(red, green, blue, alpha)
+	}
+}
+
+impl FromStr for Color {
+	type Err = ParseColorError;
+
+	fn from_str(s: &str) -> Result<Self, Self::Err> {
+		Self::hex(s)
+	}
+}
+
+impl From<Color> for wgpu::Color {
+	fn from(color: Color) -> Self {
+		let [r, g, b, a] = color.to_linear().map(f64::from);
+		wgpu::Color { r, g, b, a }
+	}
+}
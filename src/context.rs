@@ -6,6 +6,10 @@ impl<T> Context<T> {
 	pub fn new(stage: T) -> Self {
 		Self { stage }
 	}
+
+	pub fn into_inner(self) -> T {
+		self.stage
+	}
 }
 
 impl<T> std::ops::Deref for Context<T> {
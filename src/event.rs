@@ -0,0 +1,294 @@
+use crate::{
+	layout::Layout,
+	view::{PhysicalPosition, PhysicalSize, View},
+	widget::Widget,
+};
+
+/// Something that happened to the window or one of its input devices.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Event {
+	/// The cursor moved to a position relative to the top-left of the window.
+	CursorMoved(PhysicalPosition<f64>),
+	CursorLeft,
+	MouseInput {
+		button: MouseButton,
+		state: ElementState,
+	},
+	MouseWheel(MouseScrollDelta),
+	Keyboard {
+		key: Key,
+		state: ElementState,
+	},
+	/// A character was typed, this comes after the `Keyboard` event of the keys that typed it.
+	Text(char),
+	Ime(Ime),
+	ModifiersChanged(Modifiers),
+	Focused(bool),
+	Resized(PhysicalSize<u32>),
+	CloseRequested,
+	/// The application was suspended, and should not draw until it is resumed.
+	Suspended,
+	Resumed,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ElementState {
+	Pressed,
+	Released,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum MouseButton {
+	Left,
+	Right,
+	Middle,
+	Other(u16),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum MouseScrollDelta {
+	/// A number of lines and columns to scroll, as given by a mouse wheel.
+	LineDelta(f32, f32),
+	/// A number of pixels to scroll, as given by a touchpad.
+	PixelDelta(PhysicalPosition<f64>),
+}
+
+/// Text being composed with an input method.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum Ime {
+	Enabled,
+	/// The text that is being composed, with the range of the cursor in it as byte offsets.
+	Preedit(String, Option<(usize, usize)>),
+	/// The composed text, which replaces the text that was being composed.
+	Commit(String),
+	Disabled,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct Modifiers {
+	pub shift: bool,
+	pub ctrl: bool,
+	pub alt: bool,
+	/// The windows or command key.
+	pub logo: bool,
+}
+
+macro_rules! keys {
+	($($key:ident),* $(,)?) => {
+		/// A key on the keyboard, by what it means in the current keyboard layout.
+		#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+		pub enum Key {
+			$($key,)*
+			/// Any other key, by its scancode.
+			Other(u32),
+		}
+
+		#[cfg(feature = "window")]
+		impl Key {
+			pub(crate) fn from_winit(key: Option<winit::event::VirtualKeyCode>, scancode: u32) -> Self {
+				match key {
+					$(Some(winit::event::VirtualKeyCode::$key) => Key::$key,)*
+					_ => Key::Other(scancode),
+				}
+			}
+		}
+	};
+}
+
+keys! {
+	Escape, Tab, Back, Return, Space, Insert, Delete, Home, End, PageUp, PageDown,
+	Left, Right, Up, Down,
+	Key0, Key1, Key2, Key3, Key4, Key5, Key6, Key7, Key8, Key9,
+	A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P, Q, R, S, T, U, V, W, X, Y, Z,
+	F1, F2, F3, F4, F5, F6, F7, F8, F9, F10, F11, F12,
+}
+
+/// Whether a pointer event should also be handled by the widgets around the one that handled it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Propagation {
+	Continue,
+	Stop,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum PointerKind {
+	Moved,
+	/// The cursor is no longer over the widget, this is sent to every widget it left, and can't
+	/// be stopped.
+	Exited,
+	Button {
+		button: MouseButton,
+		state: ElementState,
+	},
+	Wheel(MouseScrollDelta),
+}
+
+/// An event of the mouse cursor, which is only sent to the widgets under it.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PointerEvent {
+	kind: PointerKind,
+	position: PhysicalPosition<f64>,
+	previous: Option<PhysicalPosition<f64>>,
+	origin: PhysicalPosition<f64>,
+}
+
+impl PointerEvent {
+	/// An event with the cursor at `position` in the window, `previous` is only used by
+	/// `PointerKind::Moved` to find the widgets the cursor left.
+	pub fn new(
+		kind: PointerKind,
+		position: PhysicalPosition<f64>,
+		previous: Option<PhysicalPosition<f64>>,
+	) -> Self {
+		Self {
+			kind,
+			position,
+			previous,
+			origin: PhysicalPosition::new(0.0, 0.0),
+		}
+	}
+
+	pub fn kind(&self) -> PointerKind {
+		self.kind
+	}
+
+	/// The position of the cursor relative to the top-left of the widget handling this event.
+	pub fn position(&self) -> PhysicalPosition<f64> {
+		PhysicalPosition::new(
+			self.position.x - self.origin.x,
+			self.position.y - self.origin.y,
+		)
+	}
+
+	pub fn window_position(&self) -> PhysicalPosition<f64> {
+		self.position
+	}
+
+	fn at(&self, view: &View) -> Self {
+		let position = view.position();
+		Self {
+			origin: PhysicalPosition::new(position.x as f64, position.y as f64),
+			..*self
+		}
+	}
+
+	fn exited(&self) -> Option<Self> {
+		match self.kind {
+			PointerKind::Moved => Some(Self {
+				kind: PointerKind::Exited,
+				position: self.previous?,
+				previous: None,
+				..*self
+			}),
+			_ => None,
+		}
+	}
+}
+
+/// Anything a pointer event can be routed to.
+pub trait PointerTarget {
+	fn receive(&mut self, event: &PointerEvent, layout: &Layout) -> Propagation;
+}
+
+impl<T: Widget> PointerTarget for T {
+	fn receive(&mut self, event: &PointerEvent, layout: &Layout) -> Propagation {
+		self.handle_pointer(event, layout)
+	}
+}
+
+pub(crate) fn contains(view: &View, position: PhysicalPosition<f64>) -> bool {
+	let top_left = view.position();
+	let (x, y) = (
+		position.x - top_left.x as f64,
+		position.y - top_left.y as f64,
+	);
+	x >= 0.0 && y >= 0.0 && x < view.width() as f64 && y < view.height() as f64
+}
+
+/// Send `event` to the child under the cursor, and tell the child the cursor left when it moved
+/// away from it.
+pub fn route<'a, T>(
+	event: &PointerEvent,
+	children: impl Iterator<Item = (&'a mut T, &'a Layout)>,
+) -> Propagation
+where
+	T: PointerTarget + ?Sized + 'a,
+{
+	let mut children = children.collect::<Vec<_>>();
+
+	// The child on top is the last one that contains the position.
+	let top = |children: &[(&mut T, &Layout)], position| {
+		children
+			.iter()
+			.rposition(|(_, layout)| contains(layout.view(), position))
+	};
+
+	let hit = top(&children, event.position);
+
+	// The child the cursor was over before it moved, which is told it left before the event
+	// reaches the child the cursor moved to.
+	if let Some(exited) = event.exited() {
+		match top(&children, exited.position) {
+			Some(index) if Some(index) != hit => {
+				let (child, layout) = &mut children[index];
+				child.receive(&exited.at(layout.view()), layout);
+			}
+			_ => {}
+		}
+	}
+
+	match hit {
+		Some(index) => {
+			let (child, layout) = &mut children[index];
+			child.receive(&event.at(layout.view()), layout)
+		}
+		None => Propagation::Continue,
+	}
+}
+
+/// Send `event` to `widget` and the children under the cursor, starting with the innermost one.
+pub fn dispatch<T: Widget>(widget: &mut T, layout: &Layout, event: &PointerEvent) -> Propagation {
+	route(event, std::iter::once((widget, layout)))
+}
+
+/// Remembers where the cursor is, to turn the mouse events of a window into pointer events.
+#[derive(Debug, Default)]
+pub struct Pointer {
+	position: Option<PhysicalPosition<f64>>,
+}
+
+impl Pointer {
+	pub fn new() -> Self {
+		Self::default()
+	}
+
+	/// Whether `event` is handled as a pointer event instead of being sent to every widget.
+	pub fn is_pointer_event(event: &Event) -> bool {
+		matches!(
+			event,
+			Event::CursorMoved(_)
+				| Event::CursorLeft
+				| Event::MouseInput { .. }
+				| Event::MouseWheel(_)
+		)
+	}
+
+	/// The pointer event for `event`, if it is one and the cursor is in the window.
+	pub fn event(&mut self, event: &Event) -> Option<PointerEvent> {
+		let (kind, position, previous) = match *event {
+			Event::CursorMoved(position) => (
+				PointerKind::Moved,
+				position,
+				self.position.replace(position),
+			),
+			Event::CursorLeft => (PointerKind::Exited, self.position.take()?, None),
+			Event::MouseInput { state, button } => {
+				(PointerKind::Button { button, state }, self.position?, None)
+			}
+			Event::MouseWheel(delta) => (PointerKind::Wheel(delta), self.position?, None),
+			_ => return None,
+		};
+
+		Some(PointerEvent::new(kind, position, previous))
+	}
+}
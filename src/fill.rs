@@ -0,0 +1,143 @@
+use crate::{
+	color::Color,
+	render::Vertex,
+	view::{View, VirtualPosition},
+};
+
+/// How many quads a gradient is split into along each side, the colors are only exact at their
+/// corners and blended in between.
+const GRADIENT_STEPS: u32 = 32;
+
+/// How the area of a view is colored.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Fill {
+	Solid(Color),
+	/// Colors that change along the line from `start` to `end`, with `stops` giving the color at
+	/// a fraction of that line.
+	Linear {
+		start: (f32, f32),
+		end: (f32, f32),
+		stops: Vec<(f32, Color)>,
+	},
+	/// Colors that change with the distance from `center`, with `stops` giving the color at a
+	/// fraction of `radius`.
+	Radial {
+		center: (f32, f32),
+		radius: f32,
+		stops: Vec<(f32, Color)>,
+	},
+}
+
+impl Fill {
+	pub fn vertical(top: Color, bottom: Color) -> Self {
+		Fill::Linear {
+			start: (0.0, 0.0),
+			end: (0.0, 1.0),
+			stops: vec![(0.0, top), (1.0, bottom)],
+		}
+	}
+
+	pub fn horizontal(left: Color, right: Color) -> Self {
+		Fill::Linear {
+			start: (0.0, 0.0),
+			end: (1.0, 0.0),
+			stops: vec![(0.0, left), (1.0, right)],
+		}
+	}
+
+	/// The color at `(x, y)` in virtual coordinates, in linear space.
+	pub fn color_at(&self, x: f32, y: f32) -> [f32; 4] {
+		match self {
+			Fill::Solid(color) => color.to_linear(),
+			Fill::Linear { start, end, stops } => {
+				let direction = (end.0 - start.0, end.1 - start.1);
+				let length = direction.0 * direction.0 + direction.1 * direction.1;
+				let t = if length > 0.0 {
+					((x - start.0) * direction.0 + (y - start.1) * direction.1) / length
+				} else {
+					0.0
+				};
+				gradient(stops, t)
+			}
+			Fill::Radial {
+				center,
+				radius,
+				stops,
+			} => {
+				let distance = (x - center.0).hypot(y - center.1);
+				let t = if *radius > 0.0 {
+					distance / radius
+				} else {
+					1.0
+				};
+				gradient(stops, t)
+			}
+		}
+	}
+
+	/// The vertices and indices of a mesh that covers `view` with this fill.
+	pub fn mesh(&self, view: &View) -> (Vec<Vertex>, Vec<u32>) {
+		let steps = match self {
+			Fill::Solid(_) => 1,
+			_ => GRADIENT_STEPS,
+		};
+
+		let mut vertices = vec![];
+		for row in 0..=steps {
+			for column in 0..=steps {
+				let (x, y) = (column as f32 / steps as f32, row as f32 / steps as f32);
+				vertices.push(
+					Vertex::new(view.globalize(VirtualPosition::new(x, y)), [x, y])
+						.with_color(self.color_at(x, y)),
+				);
+			}
+		}
+
+		// The corners of every quad, counter clockwise from the top-left like `View::corners`.
+		let mut indices = vec![];
+		for row in 0..steps {
+			for column in 0..steps {
+				let top_left = row * (steps + 1) + column;
+				let bottom_left = top_left + steps + 1;
+				indices.extend([
+					top_left,
+					bottom_left,
+					bottom_left + 1,
+					bottom_left + 1,
+					top_left + 1,
+					top_left,
+				]);
+			}
+		}
+
+		(vertices, indices)
+	}
+}
+
+impl From<Color> for Fill {
+	fn from(color: Color) -> Self {
+		Fill::Solid(color)
+	}
+}
+
+fn gradient(stops: &[(f32, Color)], t: f32) -> [f32; 4] {
+	let Some((first, last)) = stops.first().zip(stops.last()) else {
+		return Color::TRANSPARENT.to_linear();
+	};
+	if t <= first.0 {
+		return first.1.to_linear();
+	}
+
+	for pair in stops.windows(2) {
+		let ((from, a), (to, b)) = (pair[0], pair[1]);
+		if t <= to {
+			let amount = if to > from {
+				(t - from) / (to - from)
+			} else {
+				1.0
+			};
+			return a.mix(b, amount).to_linear();
+		}
+	}
+	last.1.to_linear()
+}
@@ -0,0 +1,283 @@
+use crate::{
+	event::{contains, ElementState, Event, Key, Modifiers, PointerEvent, PointerKind},
+	layout::Layout,
+	view::PhysicalPosition,
+	widget::{Visitor, Widget},
+};
+
+/// Where a widget is in the tree: the key of every widget from the root down to it.
+pub type Path = Vec<usize>;
+
+/// Keeps track of which widget has keyboard focus, and sends keyboard events only to it.
+#[derive(Debug, Default)]
+pub struct Focus {
+	focused: Option<Path>,
+	modifiers: Modifiers,
+}
+
+impl Focus {
+	pub fn new() -> Self {
+		Self::default()
+	}
+
+	/// The index of the focused widget among the focusable widgets in `widget`, if any.
+	pub fn focused<T: Widget>(&self, widget: &mut T, layout: &Layout) -> Option<usize> {
+		let path = self.focused.as_ref()?;
+		walk(widget, layout, Target::Path(path), None).map(|(index, _)| index)
+	}
+
+	/// Whether `event` is only sent to the focused widget.
+	pub fn is_key_event(event: &Event) -> bool {
+		matches!(
+			event,
+			Event::Keyboard { .. } | Event::Text(_) | Event::Ime(_) | Event::ModifiersChanged(_)
+		)
+	}
+
+	/// Give focus to the widget at `index`, or to none of them, telling both the widget that lost
+	/// focus and the one that gained it.
+	pub fn focus<T: Widget>(&mut self, widget: &mut T, layout: &Layout, index: Option<usize>) {
+		let path = index.and_then(|index| {
+			walk(widget, layout, Target::Index(index), None).map(|(_, path)| path)
+		});
+		self.focus_path(widget, layout, path);
+	}
+
+	fn focus_path<T: Widget>(&mut self, widget: &mut T, layout: &Layout, path: Option<Path>) {
+		if path == self.focused {
+			return;
+		}
+
+		if let Some(previous) = self.focused.take() {
+			walk(
+				widget,
+				layout,
+				Target::Path(&previous),
+				Some(Action::Focus(false)),
+			);
+		}
+		if let Some(path) = &path {
+			walk(
+				widget,
+				layout,
+				Target::Path(path),
+				Some(Action::Focus(true)),
+			);
+		}
+		self.focused = path;
+	}
+
+	/// Move focus to the next focusable widget, going back to the first one after the last.
+	pub fn next<T: Widget>(&mut self, widget: &mut T, layout: &Layout) {
+		let count = count(widget, layout);
+		let next = match self.focused(widget, layout) {
+			_ if count == 0 => None,
+			Some(index) => Some((index + 1) % count),
+			None => Some(0),
+		};
+		self.focus(widget, layout, next);
+	}
+
+	/// Move focus to the previous focusable widget, going to the last one before the first.
+	pub fn previous<T: Widget>(&mut self, widget: &mut T, layout: &Layout) {
+		let count = count(widget, layout);
+		let previous = match self.focused(widget, layout) {
+			_ if count == 0 => None,
+			Some(index) => Some((index + count - 1) % count),
+			None => Some(count - 1),
+		};
+		self.focus(widget, layout, previous);
+	}
+
+	/// Forget about the focused widget when it is no longer part of `widget`, this is called
+	/// every time the widget has been laid out again.
+	pub fn update<T: Widget>(&mut self, widget: &mut T, layout: &Layout) {
+		if let Some(path) = &self.focused {
+			if walk(widget, layout, Target::Path(path), None).is_none() {
+				self.focused = None;
+			}
+		}
+	}
+
+	/// Move focus with Tab and Shift+Tab, and send every other keyboard event to the focused
+	/// widget.
+	pub fn handle_key<T: Widget>(&mut self, widget: &mut T, layout: &Layout, event: &Event) {
+		match event {
+			Event::ModifiersChanged(modifiers) => self.modifiers = *modifiers,
+			Event::Keyboard {
+				key: Key::Tab,
+				state,
+			} => {
+				if *state == ElementState::Pressed {
+					if self.modifiers.shift {
+						self.previous(widget, layout);
+					} else {
+						self.next(widget, layout);
+					}
+				}
+				return;
+			}
+			Event::Text('\t') => return,
+			_ => {}
+		}
+
+		if let Some(path) = &self.focused {
+			walk(widget, layout, Target::Path(path), Some(Action::Key(event)));
+		}
+	}
+
+	/// Focus the innermost focusable widget under the cursor when a mouse button is pressed.
+	pub fn handle_pointer<T: Widget>(
+		&mut self,
+		widget: &mut T,
+		layout: &Layout,
+		event: &PointerEvent,
+	) {
+		if let PointerKind::Button {
+			state: ElementState::Pressed,
+			..
+		} = event.kind()
+		{
+			let target = Target::Position(event.window_position());
+			let found = walk(widget, layout, target, None).map(|(_, path)| path);
+			self.focus_path(widget, layout, found);
+		}
+	}
+}
+
+/// The number of focusable widgets in `widget`, including itself.
+pub fn count<T: Widget>(widget: &mut T, layout: &Layout) -> usize {
+	let mut counter = Count(0);
+	counter.visit(widget, layout);
+	counter.0
+}
+
+struct Count(usize);
+
+impl Visitor for Count {
+	fn visit<T: Widget>(&mut self, child: &mut T, layout: &Layout) {
+		if child.focusable() {
+			self.0 += 1;
+		}
+		child.visit_children(layout, self);
+	}
+}
+
+/// Tell every focusable widget in `widget` that it lost focus, for containers that take
+/// `widget` out of the tree.
+pub fn blur<T: Widget>(widget: &mut T, layout: &Layout) {
+	Blur.visit(widget, layout);
+}
+
+struct Blur;
+
+impl Visitor for Blur {
+	fn visit<T: Widget>(&mut self, child: &mut T, layout: &Layout) {
+		if child.focusable() {
+			child.focus_changed(false);
+		}
+		child.visit_children(layout, self);
+	}
+}
+
+enum Action<'a> {
+	Focus(bool),
+	Key(&'a Event),
+}
+
+enum Target<'a> {
+	Index(usize),
+	Path(&'a [usize]),
+	Position(PhysicalPosition<f64>),
+}
+
+fn walk<T: Widget>(
+	widget: &mut T,
+	layout: &Layout,
+	target: Target,
+	action: Option<Action>,
+) -> Option<(usize, Path)> {
+	let mut walk = Walk {
+		target,
+		action,
+		path: vec![],
+		siblings: vec![0],
+		index: 0,
+		found: None,
+	};
+	walk.visit(widget, layout);
+	walk.found
+}
+
+struct Walk<'a> {
+	target: Target<'a>,
+	action: Option<Action<'a>>,
+	path: Path,
+	siblings: Vec<usize>,
+	index: usize,
+	found: Option<(usize, Path)>,
+}
+
+impl Walk<'_> {
+	fn next_sibling(&mut self) -> usize {
+		let siblings = self
+			.siblings
+			.last_mut()
+			.expect("a walk always has a level for the next widget");
+		*siblings += 1;
+		*siblings - 1
+	}
+
+	fn enter<T: Widget>(&mut self, key: usize, child: &mut T, layout: &Layout) {
+		let done = match self.target {
+			Target::Position(_) => false,
+			_ => self.found.is_some(),
+		};
+		if done {
+			return;
+		}
+
+		if let Target::Position(position) = self.target {
+			// Children are only visible within their parent, so there is no need to look further.
+			if !contains(layout.view(), position) {
+				self.index += count(child, layout);
+				return;
+			}
+		}
+
+		self.path.push(key);
+		if child.focusable() {
+			let found = match self.target {
+				Target::Index(index) => index == self.index,
+				Target::Path(path) => path == self.path,
+				Target::Position(_) => true,
+			};
+			if found {
+				self.found = Some((self.index, self.path.clone()));
+				match self.action.take() {
+					Some(Action::Focus(focused)) => child.focus_changed(focused),
+					Some(Action::Key(event)) => child.handle_key(event),
+					None => {}
+				}
+			}
+			self.index += 1;
+		}
+
+		self.siblings.push(0);
+		child.visit_children(layout, self);
+		self.siblings.pop();
+		self.path.pop();
+	}
+}
+
+impl Visitor for Walk<'_> {
+	fn visit<T: Widget>(&mut self, child: &mut T, layout: &Layout) {
+		let key = self.next_sibling();
+		self.enter(key, child, layout);
+	}
+
+	fn visit_keyed<T: Widget>(&mut self, key: usize, child: &mut T, layout: &Layout) {
+		self.next_sibling();
+		self.enter(key, child, layout);
+	}
+}
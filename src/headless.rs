@@ -0,0 +1,251 @@
+use thiserror::Error;
+
+use crate::{color::Color, renderer::Renderer, text::Font, view::PhysicalSize, widget::Widget};
+
+pub type Result<T> = std::result::Result<T, Error>;
+
+#[derive(Debug, Error)]
+pub enum Error {
+	#[error("Could not find an adapter")]
+	AdapterNotFound,
+
+	#[error("Could not request a device: `{0}`")]
+	RequestDeviceError(#[from] wgpu::RequestDeviceError),
+
+	#[error("The supplied font file is not of a valid TTF format")]
+	InvalidFont(#[from] ab_glyph::InvalidFont),
+
+	#[error("Could not read back the rendered image: `{0}`")]
+	BufferAsyncError(#[from] wgpu::BufferAsyncError),
+
+	#[error("Could not encode the rendered image as a png: `{0}`")]
+	EncodingError(#[from] png::EncodingError),
+
+	#[error("Could not decode the png image: `{0}`")]
+	DecodingError(#[from] png::DecodingError),
+
+	#[error("Only 8 bit RGBA png images are supported")]
+	UnsupportedPng,
+}
+
+const FORMAT: wgpu::TextureFormat = wgpu::TextureFormat::Rgba8UnormSrgb;
+
+/// Renders widgets into an offscreen texture instead of a window.
+pub struct HeadlessRenderer {
+	device: wgpu::Device,
+	queue: wgpu::Queue,
+	renderer: Renderer,
+}
+
+impl HeadlessRenderer {
+	pub async fn new(force_fallback_adapter: bool) -> Result<Self> {
+		let instance = wgpu::Instance::new(wgpu::InstanceDescriptor {
+			backends: wgpu::Backends::all(),
+			..Default::default()
+		});
+
+		let adapter = instance
+			.request_adapter(&wgpu::RequestAdapterOptions {
+				power_preference: wgpu::PowerPreference::default(),
+				force_fallback_adapter,
+				compatible_surface: None,
+			})
+			.await
+			.ok_or(Error::AdapterNotFound)?;
+
+		let (device, queue) = adapter
+			.request_device(
+				&wgpu::DeviceDescriptor {
+					features: wgpu::Features::empty(),
+					limits: wgpu::Limits::downlevel_defaults().using_resolution(adapter.limits()),
+					label: None,
+				},
+				None,
+			)
+			.await?;
+
+		let renderer = Renderer::new(&device, &queue, FORMAT)?;
+
+		Ok(Self {
+			device,
+			queue,
+			renderer,
+		})
+	}
+
+	pub fn set_background(&mut self, color: Color) {
+		self.renderer.set_background(color);
+	}
+
+	/// The font text is drawn with, instead of the bundled Roboto. Characters the font doesn't
+	/// have are drawn with its [fallbacks](Font::with_fallback).
+	pub fn set_font(&mut self, font: Font) {
+		self.renderer.set_font(font);
+	}
+
+	pub fn set_font_size(&mut self, size: f32) {
+		self.renderer.set_font_size(size);
+	}
+
+	/// How many pixels of the image there are for every logical pixel, as with a window on a high
+	/// density screen.
+	pub fn set_scale_factor(&mut self, factor: f32) {
+		self.renderer
+			.set_scale_factor(factor);
+	}
+
+	pub fn render<T: Widget>(&self, widget: &mut T, size: PhysicalSize<u32>) -> Result<Image> {
+		let extent = wgpu::Extent3d {
+			width: size.width,
+			height: size.height,
+			depth_or_array_layers: 1,
+		};
+
+		let texture = self
+			.device
+			.create_texture(&wgpu::TextureDescriptor {
+				label: Some("Headless Target"),
+				size: extent,
+				mip_level_count: 1,
+				sample_count: 1,
+				dimension: wgpu::TextureDimension::D2,
+				format: FORMAT,
+				usage: wgpu::TextureUsages::RENDER_ATTACHMENT | wgpu::TextureUsages::COPY_SRC,
+				view_formats: &[],
+			});
+		let texture_view = texture.create_view(&Default::default());
+
+		widget.resize(size);
+		self.renderer
+			.draw(&self.device, &self.queue, &texture_view, size, widget);
+
+		// Rows copied out of a texture have to be aligned, so the buffer can be a bit wider than
+		// the image.
+		let unpadded_bytes_per_row = 4 * size.width;
+		let alignment = wgpu::COPY_BYTES_PER_ROW_ALIGNMENT;
+		let padded_bytes_per_row = unpadded_bytes_per_row.div_ceil(alignment) * alignment;
+
+		let buffer = self
+			.device
+			.create_buffer(&wgpu::BufferDescriptor {
+				label: Some("Headless Readback"),
+				size: (padded_bytes_per_row * size.height) as u64,
+				usage: wgpu::BufferUsages::COPY_DST | wgpu::BufferUsages::MAP_READ,
+				mapped_at_creation: false,
+			});
+
+		let mut encoder = self
+			.device
+			.create_command_encoder(&Default::default());
+		encoder.copy_texture_to_buffer(
+			texture.as_image_copy(),
+			wgpu::ImageCopyBuffer {
+				buffer: &buffer,
+				layout: wgpu::ImageDataLayout {
+					offset: 0,
+					bytes_per_row: Some(padded_bytes_per_row),
+					rows_per_image: Some(size.height),
+				},
+			},
+			extent,
+		);
+		self.queue
+			.submit(Some(encoder.finish()));
+
+		let slice = buffer.slice(..);
+		let (sender, receiver) = std::sync::mpsc::channel();
+		slice.map_async(wgpu::MapMode::Read, move |result| {
+			// The receiver is only dropped once we are done waiting for this result.
+			let _ = sender.send(result);
+		});
+		self.device
+			.poll(wgpu::Maintain::Wait);
+		receiver
+			.recv()
+			.expect("the map callback is always called after waiting on the device")?;
+
+		let data = slice
+			.get_mapped_range()
+			.chunks(padded_bytes_per_row as usize)
+			.flat_map(|row| &row[..unpadded_bytes_per_row as usize])
+			.copied()
+			.collect();
+		buffer.unmap();
+
+		Ok(Image::new(size, data))
+	}
+
+	pub fn render_png<T: Widget>(
+		&self,
+		widget: &mut T,
+		size: PhysicalSize<u32>,
+	) -> Result<Vec<u8>> {
+		self.render(widget, size)?.to_png()
+	}
+}
+
+/// An RGBA image with 8 bits per channel, stored row by row from the top-left.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Image {
+	size: PhysicalSize<u32>,
+	data: Vec<u8>,
+}
+
+impl Image {
+	pub fn new(size: PhysicalSize<u32>, data: Vec<u8>) -> Self {
+		assert_eq!(
+			data.len(),
+			(4 * size.width * size.height) as usize,
+			"the image data does not match its size"
+		);
+		Self { size, data }
+	}
+
+	pub fn size(&self) -> PhysicalSize<u32> {
+		self.size
+	}
+
+	pub fn data(&self) -> &[u8] {
+		&self.data
+	}
+
+	pub fn pixel(&self, x: u32, y: u32) -> [u8; 4] {
+		let index = (4 * (y * self.size.width + x)) as usize;
+		let mut pixel = [0; 4];
+		pixel.copy_from_slice(&self.data[index..index + 4]);
+		pixel
+	}
+
+	pub fn to_png(&self) -> Result<Vec<u8>> {
+		let mut bytes = vec![];
+
+		let mut encoder = png::Encoder::new(&mut bytes, self.size.width, self.size.height);
+		encoder.set_color(png::ColorType::Rgba);
+		encoder.set_depth(png::BitDepth::Eight);
+		encoder.set_source_srgb(png::SrgbRenderingIntent::Perceptual);
+
+		let mut writer = encoder.write_header()?;
+		writer.write_image_data(&self.data)?;
+		writer.finish()?;
+
+		Ok(bytes)
+	}
+
+	pub fn from_png(bytes: &[u8]) -> Result<Self> {
+		let mut reader = png::Decoder::new(bytes).read_info()?;
+
+		let info = reader.info();
+		if info.color_type != png::ColorType::Rgba || info.bit_depth != png::BitDepth::Eight {
+			return Err(Error::UnsupportedPng);
+		}
+
+		let mut data = vec![0; reader.output_buffer_size()];
+		let frame = reader.next_frame(&mut data)?;
+		data.truncate(frame.buffer_size());
+
+		Ok(Self::new(
+			PhysicalSize::new(frame.width, frame.height),
+			data,
+		))
+	}
+}
@@ -0,0 +1,238 @@
+use std::rc::Rc;
+
+use crate::{
+	text::{Font, Glyph, Paragraph, Wrap},
+	view::View,
+};
+
+/// The size text is drawn at when nothing else is set, in logical pixels.
+pub const DEFAULT_FONT_SIZE: f32 = 100.0;
+
+/// Everything a widget can use to decide its size.
+#[derive(Clone, Copy)]
+pub struct LayoutContext<'a> {
+	font: &'a Font,
+	font_size: f32,
+	scale_factor: f32,
+	wrap: Wrap,
+	max_lines: Option<usize>,
+}
+
+impl<'a> LayoutContext<'a> {
+	pub fn new(font: &'a Font) -> Self {
+		Self {
+			font,
+			font_size: DEFAULT_FONT_SIZE,
+			scale_factor: 1.0,
+			wrap: Wrap::default(),
+			max_lines: None,
+		}
+	}
+
+	pub fn with_font_size(self, size: f32) -> Self {
+		Self {
+			font_size: size,
+			..self
+		}
+	}
+
+	/// This context on a screen with `factor` physical pixels to every logical pixel.
+	pub fn with_scale_factor(self, factor: f32) -> Self {
+		Self {
+			scale_factor: factor,
+			..self
+		}
+	}
+
+	/// This context with text broken into lines by `wrap`, and cut short after `max_lines` lines.
+	pub fn with_wrap(self, wrap: Wrap, max_lines: Option<usize>) -> Self {
+		Self {
+			wrap,
+			max_lines,
+			..self
+		}
+	}
+
+	pub fn font(&self) -> &'a Font {
+		self.font
+	}
+
+	pub fn font_size(&self) -> f32 {
+		self.font_size
+	}
+
+	pub fn scale_factor(&self) -> f32 {
+		self.scale_factor
+	}
+
+	pub fn wrap(&self) -> Wrap {
+		self.wrap
+	}
+
+	pub fn max_lines(&self) -> Option<usize> {
+		self.max_lines
+	}
+
+	/// The glyph of `value` at the current font size, scaled to physical pixels.
+	pub fn glyph(&self, value: char) -> Glyph {
+		self.font
+			.glyph(value, self.font_size * self.scale_factor)
+	}
+
+	/// `text` at the current font size, scaled to physical pixels, and broken into lines that
+	/// fit `width` pixels, see [`Font::paragraph`].
+	pub fn paragraph(&self, text: &str, width: Option<u32>) -> Rc<Paragraph> {
+		self.font.paragraph(
+			text,
+			self.font_size * self.scale_factor,
+			width,
+			self.wrap,
+			self.max_lines,
+		)
+	}
+}
+
+/// The resolved position and size of a widget, along with the layouts of its children.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Layout {
+	view: View,
+	children: Vec<Layout>,
+}
+
+impl Layout {
+	pub fn new(view: View, children: Vec<Layout>) -> Self {
+		Self { view, children }
+	}
+
+	pub fn leaf(view: View) -> Self {
+		Self::new(view, vec![])
+	}
+
+	pub fn view(&self) -> &View {
+		&self.view
+	}
+
+	pub fn children(&self) -> &[Layout] {
+		&self.children
+	}
+}
+
+/// How the space that is left along the main axis of a container is divided around its values.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Justify {
+	#[default]
+	Start,
+	Center,
+	End,
+	/// Put the space between the values, with the first and last value against the edges.
+	SpaceBetween,
+	/// Give every value the same space on both sides of it.
+	SpaceAround,
+	/// Make every gap the same, including the ones at the edges.
+	SpaceEvenly,
+}
+
+impl Justify {
+	/// The space before the first value, and the space between every two values, when `free`
+	/// pixels are left by `count` values.
+	pub fn spacing(self, free: u32, count: usize) -> (f32, f32) {
+		let free = free as f32;
+		let count = count as f32;
+
+		match self {
+			Justify::Start => (0.0, 0.0),
+			Justify::Center => (free / 2.0, 0.0),
+			Justify::End => (free, 0.0),
+			Justify::SpaceBetween if count > 1.0 => (0.0, free / (count - 1.0)),
+			Justify::SpaceBetween => (0.0, 0.0),
+			Justify::SpaceAround if count > 0.0 => (free / count / 2.0, free / count),
+			Justify::SpaceAround => (0.0, 0.0),
+			Justify::SpaceEvenly => (free / (count + 1.0), free / (count + 1.0)),
+		}
+	}
+}
+
+/// Where the values of a container are placed along its cross axis.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Align {
+	#[default]
+	Start,
+	Center,
+	End,
+	/// Offer every value all of the space, so values that are flexible grow to fill it.
+	Stretch,
+	/// Line up the baselines of the values, values without a baseline are aligned by their
+	/// bottom edge.
+	Baseline,
+}
+
+impl Align {
+	/// The offset and size of a value that would like to be `size` pixels large within
+	/// `available` pixels.
+	pub fn place(
+		self,
+		available: u32,
+		size: u32,
+		baseline: Option<u32>,
+		line_baseline: u32,
+	) -> (u32, u32) {
+		let size = size.min(available);
+		match self {
+			Align::Start => (0, size),
+			Align::Center => ((available - size) / 2, size),
+			Align::End => (available - size, size),
+			Align::Stretch => (0, available),
+			Align::Baseline => (
+				line_baseline
+					.saturating_sub(baseline.unwrap_or(size))
+					.min(available - size),
+				size,
+			),
+		}
+	}
+}
+
+/// The size of a row or column of a grid.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Track {
+	Physical(u32),
+	/// A fraction of the size of the grid.
+	Virtual(f32),
+	/// As large as the largest value in the track, values that span multiple tracks are not
+	/// taken into account.
+	Auto,
+	/// A share of the space that is left after the other tracks have been placed.
+	Flex(f32),
+}
+
+/// The point of a stack a layer is attached to.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Anchor {
+	#[default]
+	TopLeft,
+	Top,
+	TopRight,
+	Left,
+	Center,
+	Right,
+	BottomLeft,
+	Bottom,
+	BottomRight,
+}
+
+impl Anchor {
+	/// How a layer with this anchor is aligned horizontally and vertically.
+	pub fn alignment(self) -> (Align, Align) {
+		match self {
+			Anchor::TopLeft => (Align::Start, Align::Start),
+			Anchor::Top => (Align::Center, Align::Start),
+			Anchor::TopRight => (Align::End, Align::Start),
+			Anchor::Left => (Align::Start, Align::Center),
+			Anchor::Center => (Align::Center, Align::Center),
+			Anchor::Right => (Align::End, Align::Center),
+			Anchor::BottomLeft => (Align::Start, Align::End),
+			Anchor::Bottom => (Align::Center, Align::End),
+			Anchor::BottomRight => (Align::End, Align::End),
+		}
+	}
+}
@@ -1,11 +1,29 @@
+pub mod color;
 pub mod context;
+pub mod event;
+pub mod fill;
+pub mod focus;
+pub mod layout;
 pub mod render;
+pub mod shape;
 pub mod texture;
 pub mod view;
 pub mod widget;
 
+#[cfg(any(feature = "window", feature = "headless"))]
+mod renderer;
+
 #[cfg(feature = "window")]
 pub mod window;
 
+#[cfg(feature = "text")]
+pub mod atlas;
+
 #[cfg(feature = "text")]
 pub mod text;
+
+#[cfg(feature = "headless")]
+pub mod headless;
+
+#[cfg(feature = "testing")]
+pub mod testing;
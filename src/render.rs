@@ -1,6 +1,11 @@
+use std::rc::Rc;
+
 use wgpu::util::DeviceExt;
 
-use crate::{context::Context, view::GlobalPosition};
+use crate::{
+	context::Context,
+	view::{GlobalPosition, PhysicalPosition, PhysicalSize, View},
+};
 
 pub trait Render {
 	fn render<'a, 'b>(&'a self, context: &mut Context<RenderContext<'b>>)
@@ -13,11 +18,12 @@ pub trait Render {
 pub struct Vertex {
 	position: GlobalPosition,
 	uv: [f32; 2],
+	color: [f32; 4],
 }
 
 impl Vertex {
-	const LAYOUT: [wgpu::VertexAttribute; 2] =
-		wgpu::vertex_attr_array![0 => Float32x2, 1 => Float32x2];
+	const LAYOUT: [wgpu::VertexAttribute; 3] =
+		wgpu::vertex_attr_array![0 => Float32x2, 1 => Float32x2, 2 => Float32x4];
 
 	pub fn layout() -> wgpu::VertexBufferLayout<'static> {
 		wgpu::VertexBufferLayout {
@@ -27,58 +33,346 @@ impl Vertex {
 		}
 	}
 
+	/// A vertex that shows the texture as it is.
 	pub fn new(position: GlobalPosition, uv: [f32; 2]) -> Self {
-		Self { position, uv }
+		Self {
+			position,
+			uv,
+			color: [1.0; 4],
+		}
+	}
+
+	/// This vertex with the texture multiplied by `color`, which is in linear space.
+	pub fn with_color(self, color: [f32; 4]) -> Self {
+		Self { color, ..self }
+	}
+}
+
+/// A vertex of a `Shape`, which is drawn by the distance of every pixel to the edge of the shape.
+#[repr(C)]
+#[derive(Debug, Clone, Copy, bytemuck::Pod, bytemuck::Zeroable)]
+pub struct ShapeVertex {
+	position: GlobalPosition,
+	uv: [f32; 2],
+	color: [f32; 4],
+	size: [f32; 2],
+	radii: [f32; 4],
+	border_width: f32,
+	border_color: [f32; 4],
+	blur: f32,
+}
+
+impl ShapeVertex {
+	const LAYOUT: [wgpu::VertexAttribute; 8] = wgpu::vertex_attr_array![
+		0 => Float32x2,
+		1 => Float32x2,
+		2 => Float32x4,
+		3 => Float32x2,
+		4 => Float32x4,
+		5 => Float32,
+		6 => Float32x4,
+		7 => Float32
+	];
+
+	pub fn layout() -> wgpu::VertexBufferLayout<'static> {
+		wgpu::VertexBufferLayout {
+			array_stride: std::mem::size_of::<Self>() as u64,
+			step_mode: wgpu::VertexStepMode::Vertex,
+			attributes: &Self::LAYOUT,
+		}
+	}
+
+	/// The vertex of a shape of `size` pixels at `vertex`, whose uv is its position in the shape
+	/// and whose color is that of the fill.
+	pub fn new(
+		vertex: Vertex,
+		size: [f32; 2],
+		radii: [f32; 4],
+		border_width: f32,
+		border_color: [f32; 4],
+		blur: f32,
+	) -> Self {
+		Self {
+			position: vertex.position,
+			uv: vertex.uv,
+			color: vertex.color,
+			size,
+			radii,
+			border_width,
+			border_color,
+			blur,
+		}
+	}
+}
+
+pub struct Pipelines {
+	pub mesh: wgpu::RenderPipeline,
+	pub shape: wgpu::RenderPipeline,
+}
+
+#[derive(Debug, Clone, Copy)]
+enum Source<'a> {
+	Mesh(&'a wgpu::BindGroup),
+	Shape,
+}
+
+impl PartialEq for Source<'_> {
+	fn eq(&self, other: &Self) -> bool {
+		match (self, other) {
+			(Source::Mesh(a), Source::Mesh(b)) => std::ptr::eq(*a, *b),
+			(Source::Shape, Source::Shape) => true,
+			_ => false,
+		}
+	}
+}
+
+type Clip = (PhysicalPosition<u32>, PhysicalPosition<u32>);
+
+#[derive(Debug)]
+struct Draw<'a> {
+	source: Source<'a>,
+	clip: Clip,
+	indices: std::ops::Range<u32>,
+}
+
+/// Everything that is drawn in a frame, which is uploaded to the gpu at once.
+#[derive(Debug, Default)]
+pub struct Batch<'a> {
+	vertices: Vec<Vertex>,
+	shape_vertices: Vec<ShapeVertex>,
+	indices: Vec<u32>,
+	draws: Vec<Draw<'a>>,
+}
+
+impl<'a> Batch<'a> {
+	pub fn new() -> Self {
+		Self::default()
+	}
+
+	/// The indices of everything in this batch, each offset into the vertices of its own kind.
+	pub fn indices(&self) -> &[u32] {
+		&self.indices
+	}
+
+	/// The ranges of `indices` that are drawn with a single call each, in the order they are drawn.
+	pub fn draws(&self) -> impl Iterator<Item = std::ops::Range<u32>> + '_ {
+		self.draws
+			.iter()
+			.map(|x| x.indices.clone())
+	}
+
+	fn push_mesh(&mut self, texture: &'a wgpu::BindGroup, clip: Clip, mesh: &RenderedMesh) {
+		let base = self.vertices.len();
+		self.vertices
+			.extend_from_slice(&mesh.vertices);
+		self.push(Source::Mesh(texture), clip, base, &mesh.indices);
+	}
+
+	fn push_shape(&mut self, clip: Clip, shape: &RenderedShape) {
+		let base = self.shape_vertices.len();
+		self.shape_vertices
+			.extend_from_slice(&shape.vertices);
+		self.push(Source::Shape, clip, base, &shape.indices);
+	}
+
+	fn push(&mut self, source: Source<'a>, clip: Clip, base: usize, indices: &[u32]) {
+		let start = self.indices.len() as u32;
+		self.indices.extend(
+			indices
+				.iter()
+				.map(|&x| base as u32 + x),
+		);
+		let end = self.indices.len() as u32;
+
+		match self.draws.last_mut() {
+			Some(last) if last.source == source && last.clip == clip => last.indices.end = end,
+			_ => self.draws.push(Draw {
+				source,
+				clip,
+				indices: start..end,
+			}),
+		}
+	}
+
+	pub fn upload(&self, device: &wgpu::Device) -> BatchBuffers {
+		let buffer = |label, contents: &[u8], usage| {
+			(!contents.is_empty()).then(|| {
+				device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+					label: Some(label),
+					contents,
+					usage,
+				})
+			})
+		};
+
+		BatchBuffers {
+			vertices: buffer(
+				"Vertex Buffer",
+				bytemuck::cast_slice(&self.vertices),
+				wgpu::BufferUsages::VERTEX,
+			),
+			shape_vertices: buffer(
+				"Shape Vertex Buffer",
+				bytemuck::cast_slice(&self.shape_vertices),
+				wgpu::BufferUsages::VERTEX,
+			),
+			indices: buffer(
+				"Index Buffer",
+				bytemuck::cast_slice(&self.indices),
+				wgpu::BufferUsages::INDEX,
+			),
+		}
 	}
+
+	/// Draw this batch to `pass`, with the `buffers` it was uploaded to.
+	pub fn draw<'p>(
+		&self,
+		pass: &mut wgpu::RenderPass<'p>,
+		buffers: &'p BatchBuffers,
+		pipelines: &'p Pipelines,
+	) where
+		'a: 'p,
+	{
+		let Some(indices) = &buffers.indices else {
+			return;
+		};
+		pass.set_index_buffer(indices.slice(..), wgpu::IndexFormat::Uint32);
+
+		let mut previous: Option<&Draw> = None;
+		for draw in &self.draws {
+			match draw.source {
+				Source::Mesh(texture) => {
+					if !matches!(
+						previous,
+						Some(Draw {
+							source: Source::Mesh(_),
+							..
+						})
+					) {
+						pass.set_pipeline(&pipelines.mesh);
+						if let Some(vertices) = &buffers.vertices {
+							pass.set_vertex_buffer(0, vertices.slice(..));
+						}
+					}
+					pass.set_bind_group(0, texture, &[]);
+				}
+				Source::Shape => {
+					if !matches!(
+						previous,
+						Some(Draw {
+							source: Source::Shape,
+							..
+						})
+					) {
+						pass.set_pipeline(&pipelines.shape);
+						if let Some(vertices) = &buffers.shape_vertices {
+							pass.set_vertex_buffer(0, vertices.slice(..));
+						}
+					}
+				}
+			}
+
+			if previous.map(|x| x.clip) != Some(draw.clip) {
+				let (top_left, bottom_right) = draw.clip;
+				pass.set_scissor_rect(
+					top_left.x,
+					top_left.y,
+					bottom_right.x - top_left.x,
+					bottom_right.y - top_left.y,
+				);
+			}
+
+			pass.draw_indexed(draw.indices.clone(), 0, 0..1);
+			previous = Some(draw);
+		}
+	}
+}
+
+/// The buffers a `Batch` was uploaded to, which are missing when there was nothing to upload.
+pub struct BatchBuffers {
+	vertices: Option<wgpu::Buffer>,
+	shape_vertices: Option<wgpu::Buffer>,
+	indices: Option<wgpu::Buffer>,
 }
 
 pub struct RenderContext<'a> {
-	pass: wgpu::RenderPass<'a>,
+	batch: Batch<'a>,
+	clip: Clip,
+	blank: &'a wgpu::BindGroup,
 }
 
 impl<'a> RenderContext<'a> {
-	pub fn new(pass: wgpu::RenderPass<'a>) -> Self {
-		Self { pass }
+	/// `size` is the size of the target that is drawn to.
+	pub fn new(size: PhysicalSize<u32>, blank: &'a wgpu::BindGroup) -> Self {
+		Self {
+			batch: Batch::new(),
+			clip: (
+				PhysicalPosition::new(0, 0),
+				PhysicalPosition::new(size.width, size.height),
+			),
+			blank,
+		}
+	}
+
+	pub fn into_batch(self) -> Batch<'a> {
+		self.batch
 	}
 }
 
-pub struct RenderedMesh {
-	vertices: wgpu::Buffer,
-	indices: wgpu::Buffer,
-	num_indices: u32,
-	bind_group: wgpu::BindGroup,
+/// A renderable that can only draw within the rectangle of a view.
+pub struct Clipped<T> {
+	position: PhysicalPosition<i32>,
+	size: PhysicalSize<u32>,
+	value: T,
 }
 
-impl RenderedMesh {
-	pub fn new(
-		device: &wgpu::Device,
-		vertices: &[Vertex],
-		indices: &[u16],
-		bind_group: wgpu::BindGroup,
-	) -> Self {
-		let vertices = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
-			label: Some("Vertex Buffer"),
-			contents: bytemuck::cast_slice(vertices),
-			usage: wgpu::BufferUsages::VERTEX,
-		});
+impl<T> Clipped<T> {
+	pub fn new(view: &View, value: T) -> Self {
+		Self {
+			position: view.position(),
+			size: view.size(),
+			value,
+		}
+	}
+}
 
-		let num_indices = indices.len() as u32;
+/// A mesh of triangles, which is drawn with a texture multiplied by the colors of its vertices.
+pub struct RenderedMesh {
+	vertices: Vec<Vertex>,
+	indices: Vec<u32>,
+	bind_group: Option<Rc<wgpu::BindGroup>>,
+}
 
-		let indices = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
-			label: Some("Index Buffer"),
-			contents: bytemuck::cast_slice(indices),
-			usage: wgpu::BufferUsages::INDEX,
-		});
+impl RenderedMesh {
+	pub fn new(vertices: Vec<Vertex>, indices: Vec<u32>, bind_group: Rc<wgpu::BindGroup>) -> Self {
+		Self {
+			vertices,
+			indices,
+			bind_group: Some(bind_group),
+		}
+	}
 
+	/// A mesh without a texture, which only shows the colors of its vertices.
+	pub fn untextured(vertices: Vec<Vertex>, indices: Vec<u32>) -> Self {
 		Self {
 			vertices,
 			indices,
-			num_indices,
-			bind_group,
+			bind_group: None,
 		}
 	}
 }
 
+pub struct RenderedShape {
+	vertices: Vec<ShapeVertex>,
+	indices: Vec<u32>,
+}
+
+impl RenderedShape {
+	pub fn new(vertices: Vec<ShapeVertex>, indices: Vec<u32>) -> Self {
+		Self { vertices, indices }
+	}
+}
+
 mod impls {
 	use paste::paste;
 
@@ -89,18 +383,26 @@ mod impls {
 		where
 			'a: 'b,
 		{
+			let texture = self
+				.bind_group
+				.as_deref()
+				.unwrap_or(context.blank);
+			let clip = context.clip;
 			context
-				.pass
-				.set_bind_group(0, &self.bind_group, &[]);
-			context
-				.pass
-				.set_vertex_buffer(0, self.vertices.slice(..));
-			context
-				.pass
-				.set_index_buffer(self.indices.slice(..), wgpu::IndexFormat::Uint16);
+				.batch
+				.push_mesh(texture, clip, self);
+		}
+	}
+
+	impl Render for RenderedShape {
+		fn render<'a, 'b>(&'a self, context: &mut Context<RenderContext<'b>>)
+		where
+			'a: 'b,
+		{
+			let clip = context.clip;
 			context
-				.pass
-				.draw_indexed(0..self.num_indices, 0, 0..1);
+				.batch
+				.push_shape(clip, self);
 		}
 	}
 
@@ -118,6 +420,37 @@ mod impls {
 		}
 	}
 
+	impl<T> Render for Clipped<T>
+	where
+		T: Render,
+	{
+		fn render<'a, 'b>(&'a self, context: &mut Context<RenderContext<'b>>)
+		where
+			'a: 'b,
+		{
+			let previous = context.clip;
+
+			let clamp = |value: i32| value.max(0) as u32;
+			let top_left = PhysicalPosition::new(
+				clamp(self.position.x).max(previous.0.x),
+				clamp(self.position.y).max(previous.0.y),
+			);
+			let bottom_right = PhysicalPosition::new(
+				clamp(self.position.x + self.size.width as i32).min(previous.1.x),
+				clamp(self.position.y + self.size.height as i32).min(previous.1.y),
+			);
+
+			// Nothing of the value is visible.
+			if bottom_right.x <= top_left.x || bottom_right.y <= top_left.y {
+				return;
+			}
+
+			context.clip = (top_left, bottom_right);
+			self.value.render(context);
+			context.clip = previous;
+		}
+	}
+
 	impl<T> Render for Option<T>
 	where
 		T: Render,
@@ -0,0 +1,245 @@
+use std::cell::RefCell;
+
+use crate::{
+	atlas::GlyphAtlas,
+	color::Color,
+	context::Context,
+	layout::{Layout, LayoutContext, DEFAULT_FONT_SIZE},
+	render::{Pipelines, Render, RenderContext, ShapeVertex, Vertex},
+	text::Font,
+	texture::Texture,
+	view::{GlobalView, PhysicalPosition, PhysicalSize},
+	widget::{prepared_layout, Widget, WidgetContext},
+};
+
+/// The state shared by every render target, which the `window` and `headless` renderers build on.
+pub struct Renderer {
+	pipelines: Pipelines,
+	texture_layout: wgpu::BindGroupLayout,
+	sampler: wgpu::Sampler,
+	blank: wgpu::BindGroup,
+	font: Font,
+	atlas: RefCell<GlyphAtlas>,
+	background: Color,
+	font_size: f32,
+	scale_factor: f32,
+}
+
+impl Renderer {
+	pub fn new(
+		device: &wgpu::Device,
+		queue: &wgpu::Queue,
+		format: wgpu::TextureFormat,
+	) -> Result<Self, ab_glyph::InvalidFont> {
+		let bind_group_layout = device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+			label: None,
+			entries: &[
+				wgpu::BindGroupLayoutEntry {
+					binding: 0,
+					visibility: wgpu::ShaderStages::FRAGMENT,
+					ty: wgpu::BindingType::Texture {
+						sample_type: wgpu::TextureSampleType::Float { filterable: true },
+						view_dimension: wgpu::TextureViewDimension::D2,
+						multisampled: false,
+					},
+					count: None,
+				},
+				wgpu::BindGroupLayoutEntry {
+					binding: 1,
+					visibility: wgpu::ShaderStages::FRAGMENT,
+					ty: wgpu::BindingType::Sampler(wgpu::SamplerBindingType::Filtering),
+					count: None,
+				},
+			],
+		});
+
+		let sampler = device.create_sampler(&wgpu::SamplerDescriptor {
+			label: None,
+			address_mode_u: wgpu::AddressMode::ClampToEdge,
+			address_mode_v: wgpu::AddressMode::ClampToEdge,
+			address_mode_w: wgpu::AddressMode::ClampToEdge,
+			mag_filter: wgpu::FilterMode::Linear,
+			min_filter: wgpu::FilterMode::Linear,
+			mipmap_filter: wgpu::FilterMode::Nearest,
+			..Default::default()
+		});
+
+		let size = wgpu::Extent3d {
+			width: 1,
+			height: 1,
+			depth_or_array_layers: 1,
+		};
+		let mut blank = Texture::new(device, size, format);
+		blank.write_data(queue, &[255; 4]);
+		let blank = blank.bind_group(device, &bind_group_layout, &sampler);
+
+		let font = Font::roboto()?;
+
+		let layout = device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+			label: Some("Render Pipeline Layout"),
+			bind_group_layouts: &[&bind_group_layout],
+			push_constant_ranges: &[],
+		});
+		let mesh = pipeline(
+			device,
+			format,
+			&layout,
+			&device.create_shader_module(wgpu::include_wgsl!("shader.wgsl")),
+			Vertex::layout(),
+		);
+
+		// Shapes are not textured, so their pipeline doesn't have any bind groups.
+		let layout = device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+			label: Some("Shape Pipeline Layout"),
+			bind_group_layouts: &[],
+			push_constant_ranges: &[],
+		});
+		let shape = pipeline(
+			device,
+			format,
+			&layout,
+			&device.create_shader_module(wgpu::include_wgsl!("shape.wgsl")),
+			ShapeVertex::layout(),
+		);
+
+		let atlas = GlyphAtlas::new(device, format, &sampler, &bind_group_layout);
+
+		Ok(Self {
+			pipelines: Pipelines { mesh, shape },
+			texture_layout: bind_group_layout,
+			sampler,
+			blank,
+			font,
+			atlas: RefCell::new(atlas),
+			background: Color::linear(0.1, 0.5, 0.9, 1.0),
+			font_size: DEFAULT_FONT_SIZE,
+			scale_factor: 1.0,
+		})
+	}
+
+	pub fn set_background(&mut self, color: Color) {
+		self.background = color;
+	}
+
+	pub fn set_font(&mut self, font: Font) {
+		self.font = font;
+	}
+
+	pub fn set_font_size(&mut self, size: f32) {
+		self.font_size = size;
+	}
+
+	/// How many physical pixels the target has for every logical pixel, text is scaled by this.
+	pub fn set_scale_factor(&mut self, factor: f32) {
+		self.scale_factor = factor;
+	}
+
+	/// Render `widget` into `target`, which has to be `size` pixels large and of the format this
+	/// renderer was created with.
+	pub fn draw<T: Widget>(
+		&self,
+		device: &wgpu::Device,
+		queue: &wgpu::Queue,
+		target: &wgpu::TextureView,
+		size: PhysicalSize<u32>,
+		widget: &mut T,
+	) -> Layout {
+		let mut encoder = device.create_command_encoder(&Default::default());
+
+		let view = GlobalView::new(size).view(size, PhysicalPosition::new(0, 0));
+
+		let context = LayoutContext::new(&self.font)
+			.with_font_size(self.font_size)
+			.with_scale_factor(self.scale_factor);
+		let layout = prepared_layout(widget, &Context::new(context), view);
+
+		let mut atlas = self.atlas.borrow_mut();
+		atlas.next_frame();
+		let mut evictions = atlas.evictions();
+
+		let mut renderable = {
+			let mut context = Context::new(WidgetContext::new(context, queue, &mut atlas));
+			widget.get_renderable(&mut context, &layout)
+		};
+
+		// Glyphs that were evicted to make room for others, or moved because the atlas grew to fit
+		// every glyph of this frame, might have been drawn by renderables that were created
+		// before, which have to be created again.
+		while atlas.evictions() != evictions
+			|| atlas.is_full() && atlas.grow(device, queue, &self.sampler, &self.texture_layout)
+		{
+			evictions = atlas.evictions();
+			let mut context = Context::new(WidgetContext::new(context, queue, &mut atlas));
+			renderable = widget.get_renderable(&mut context, &layout);
+		}
+
+		let mut context = Context::new(RenderContext::new(size, &self.blank));
+		renderable.render(&mut context);
+		let batch = context.into_inner().into_batch();
+		let buffers = batch.upload(device);
+
+		{
+			let mut pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+				label: Some("Render Pass"),
+				color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+					view: target,
+					resolve_target: None,
+					ops: wgpu::Operations {
+						load: wgpu::LoadOp::Clear(self.background.into()),
+						store: true,
+					},
+				})],
+				depth_stencil_attachment: None,
+			});
+
+			batch.draw(&mut pass, &buffers, &self.pipelines);
+		}
+
+		queue.submit(Some(encoder.finish()));
+
+		layout
+	}
+}
+
+fn pipeline(
+	device: &wgpu::Device,
+	format: wgpu::TextureFormat,
+	layout: &wgpu::PipelineLayout,
+	shader: &wgpu::ShaderModule,
+	buffer: wgpu::VertexBufferLayout,
+) -> wgpu::RenderPipeline {
+	device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
+		label: Some("Render Pipeline"),
+		layout: Some(layout),
+		vertex: wgpu::VertexState {
+			module: shader,
+			entry_point: "vs_main",
+			buffers: &[buffer],
+		},
+		fragment: Some(wgpu::FragmentState {
+			module: shader,
+			entry_point: "fs_main",
+			targets: &[Some(wgpu::ColorTargetState {
+				format,
+				blend: Some(wgpu::BlendState::ALPHA_BLENDING),
+				write_mask: wgpu::ColorWrites::ALL,
+			})],
+		}),
+		primitive: wgpu::PrimitiveState {
+			topology: wgpu::PrimitiveTopology::TriangleList,
+			strip_index_format: None,
+			front_face: wgpu::FrontFace::Ccw,
+			cull_mode: Some(wgpu::Face::Back),
+			polygon_mode: wgpu::PolygonMode::Fill,
+			unclipped_depth: false,
+			conservative: false,
+		},
+		depth_stencil: None,
+		multisample: wgpu::MultisampleState {
+			count: 1,
+			mask: !0,
+			alpha_to_coverage_enabled: false,
+		},
+		multiview: None,
+	})
+}
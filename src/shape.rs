@@ -0,0 +1,219 @@
+use crate::{
+	color::Color,
+	fill::Fill,
+	render::{ShapeVertex, Vertex},
+	view::{View, VirtualPosition},
+};
+
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct Radii {
+	pub top_left: f32,
+	pub top_right: f32,
+	pub bottom_right: f32,
+	pub bottom_left: f32,
+}
+
+impl Radii {
+	/// The corners are given in clockwise order starting at the top-left.
+	pub const fn new(top_left: f32, top_right: f32, bottom_right: f32, bottom_left: f32) -> Self {
+		Self {
+			top_left,
+			top_right,
+			bottom_right,
+			bottom_left,
+		}
+	}
+
+	pub const fn all(radius: f32) -> Self {
+		Self::new(radius, radius, radius, radius)
+	}
+
+	pub fn is_zero(&self) -> bool {
+		*self == Self::default()
+	}
+
+	fn clamped(self, width: f32, height: f32) -> [f32; 4] {
+		let max = width.min(height) / 2.0;
+		[
+			self.top_left,
+			self.top_right,
+			self.bottom_right,
+			self.bottom_left,
+		]
+		.map(|x| x.clamp(0.0, max))
+	}
+}
+
+impl From<f32> for Radii {
+	fn from(radius: f32) -> Self {
+		Self::all(radius)
+	}
+}
+
+/// A rectangle with rounded corners and a border along its inside.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Shape {
+	fill: Fill,
+	radii: Radii,
+	border_width: f32,
+	border_color: Color,
+}
+
+impl Shape {
+	/// A rectangle with sharp corners and no border.
+	pub fn new(fill: impl Into<Fill>) -> Self {
+		Self {
+			fill: fill.into(),
+			radii: Radii::default(),
+			border_width: 0.0,
+			border_color: Color::TRANSPARENT,
+		}
+	}
+
+	pub fn rounded(mut self, radii: impl Into<Radii>) -> Self {
+		self.radii = radii.into();
+		self
+	}
+
+	/// Draw a border of `width` pixels in `color` along the inside of the edge of this shape.
+	pub fn outlined(mut self, width: f32, color: Color) -> Self {
+		self.border_width = width;
+		self.border_color = color;
+		self
+	}
+
+	pub fn fill(&self) -> &Fill {
+		&self.fill
+	}
+
+	pub fn radii(&self) -> Radii {
+		self.radii
+	}
+
+	pub fn border(&self) -> (f32, Color) {
+		(self.border_width, self.border_color)
+	}
+
+	/// The vertices and indices of a mesh that covers `view` with this shape, for the shape
+	/// pipeline.
+	pub fn mesh(&self, view: &View) -> (Vec<ShapeVertex>, Vec<u32>) {
+		let size = [view.width() as f32, view.height() as f32];
+		let radii = self
+			.radii
+			.clamped(size[0], size[1]);
+		let border_color = self.border_color.to_linear();
+
+		let (vertices, indices) = self.fill.mesh(view);
+		let vertices = vertices
+			.into_iter()
+			.map(|x| ShapeVertex::new(x, size, radii, self.border_width, border_color, 0.0))
+			.collect();
+
+		(vertices, indices)
+	}
+}
+
+impl From<Fill> for Shape {
+	fn from(fill: Fill) -> Self {
+		Self::new(fill)
+	}
+}
+
+impl From<Color> for Shape {
+	fn from(color: Color) -> Self {
+		Self::new(color)
+	}
+}
+
+/// The blurred silhouette of a rounded rectangle, which is drawn below it to lift it off of what
+/// is behind it.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct BoxShadow {
+	color: Color,
+	offset: (f32, f32),
+	blur: f32,
+	spread: f32,
+	radii: Radii,
+}
+
+impl BoxShadow {
+	/// A shadow exactly below the rectangle, with sharp edges and corners.
+	pub fn new(color: Color) -> Self {
+		Self {
+			color,
+			offset: (0.0, 0.0),
+			blur: 0.0,
+			spread: 0.0,
+			radii: Radii::default(),
+		}
+	}
+
+	pub fn offset(mut self, x: f32, y: f32) -> Self {
+		self.offset = (x, y);
+		self
+	}
+
+	/// Blur the edges of the shadow, which makes it reach `radius` pixels further out and fade
+	/// in over the same distance inwards.
+	pub fn blurred(mut self, radius: f32) -> Self {
+		self.blur = radius.max(0.0);
+		self
+	}
+
+	/// Grow the shadow by `spread` pixels on every side, or shrink it when this is negative.
+	pub fn spread(mut self, spread: f32) -> Self {
+		self.spread = spread;
+		self
+	}
+
+	/// Round the corners of the shadow, these should match those of the rectangle it is below.
+	/// The corners grow and shrink along with the spread.
+	pub fn rounded(mut self, radii: impl Into<Radii>) -> Self {
+		self.radii = radii.into();
+		self
+	}
+
+	/// The vertices and indices of a mesh that draws this shadow below a rectangle covering
+	/// `view`, for the shape pipeline.
+	pub fn mesh(&self, view: &View) -> (Vec<ShapeVertex>, Vec<u32>) {
+		let (width, height) = (view.width() as f32, view.height() as f32);
+		let size = [width + self.spread * 2.0, height + self.spread * 2.0];
+		if width == 0.0 || height == 0.0 || size[0] <= 0.0 || size[1] <= 0.0 {
+			return (vec![], vec![]);
+		}
+
+		let grow = |x: f32| (x + self.spread).max(0.0);
+		let radii = Radii::new(
+			grow(self.radii.top_left),
+			grow(self.radii.top_right),
+			grow(self.radii.bottom_right),
+			grow(self.radii.bottom_left),
+		)
+		.clamped(size[0], size[1]);
+		let color = self.color.to_linear();
+
+		// The top-left of the shadow relative to the view, and how far past its edges the blur
+		// is still visible, which is three standard deviations of the gaussian.
+		let origin = (self.offset.0 - self.spread, self.offset.1 - self.spread);
+		let margin = self.blur * 1.5;
+
+		// The corners of the blurred area as a position in the shadow, counter clockwise from
+		// the top-left like `View::corners`.
+		let vertices = [(0.0, 0.0), (0.0, 1.0), (1.0, 1.0), (1.0, 0.0)]
+			.map(|(u, v): (f32, f32)| {
+				let uv = [
+					(u * (size[0] + margin * 2.0) - margin) / size[0],
+					(v * (size[1] + margin * 2.0) - margin) / size[1],
+				];
+				let position = VirtualPosition::new(
+					(origin.0 + uv[0] * size[0]) / width,
+					(origin.1 + uv[1] * size[1]) / height,
+				);
+				let vertex = Vertex::new(view.globalize(position), uv).with_color(color);
+				ShapeVertex::new(vertex, size, radii, 0.0, [0.0; 4], self.blur)
+			})
+			.to_vec();
+
+		(vertices, vec![0, 1, 2, 2, 3, 0])
+	}
+}
@@ -0,0 +1,81 @@
+struct VertexOut {
+    @builtin(position) position: vec4<f32>,
+    @location(0) uv: vec2<f32>,
+    @location(1) color: vec4<f32>,
+    @location(2) size: vec2<f32>,
+    @location(3) radii: vec4<f32>,
+    @location(4) border_width: f32,
+    @location(5) border_color: vec4<f32>,
+    @location(6) blur: f32,
+};
+
+@vertex
+fn vs_main(
+    @location(0) position: vec2<f32>,
+    @location(1) uv: vec2<f32>,
+    @location(2) color: vec4<f32>,
+    @location(3) size: vec2<f32>,
+    @location(4) radii: vec4<f32>,
+    @location(5) border_width: f32,
+    @location(6) border_color: vec4<f32>,
+    @location(7) blur: f32,
+) -> VertexOut {
+    var out: VertexOut;
+    out.position = vec4<f32>(position, 0.0, 1.0);
+    out.uv = uv;
+    out.color = color;
+    out.size = size;
+    out.radii = radii;
+    out.border_width = border_width;
+    out.border_color = border_color;
+    out.blur = blur;
+    return out;
+}
+
+// The distance from `p` to the edge of a box around the origin, negative inside of it. The
+// radii are those of the top-left, top-right, bottom-right and bottom-left corner.
+fn rounded_box(p: vec2<f32>, half_size: vec2<f32>, radii: vec4<f32>) -> f32 {
+    var radius: f32;
+    if p.x < 0.0 {
+        radius = select(radii.w, radii.x, p.y < 0.0);
+    } else {
+        radius = select(radii.z, radii.y, p.y < 0.0);
+    }
+    let q = abs(p) - half_size + radius;
+    return min(max(q.x, q.y), 0.0) + length(max(q, vec2<f32>(0.0))) - radius;
+}
+
+// An approximation of the error function, with an error below 5e-4.
+fn erf(x: f32) -> f32 {
+    let a = abs(x);
+    var y = 1.0 + (0.278393 + (0.230389 + 0.078108 * a * a) * a) * a;
+    y = y * y;
+    return sign(x) * (1.0 - 1.0 / (y * y));
+}
+
+@fragment
+fn fs_main(in: VertexOut) -> @location(0) vec4<f32> {
+    let p = (in.uv - 0.5) * in.size;
+    let distance = rounded_box(p, in.size * 0.5, in.radii);
+
+    // How much of the pixel is covered, assuming the edge is straight within it. A blurred
+    // edge is the sharp one convolved with a gaussian whose standard deviation is half the blur.
+    var coverage = clamp(0.5 - distance, 0.0, 1.0);
+    if in.blur > 0.0 {
+        coverage = 0.5 - 0.5 * erf(distance / (in.blur * 0.5 * sqrt(2.0)));
+    }
+    var inside = 1.0;
+    if in.border_width > 0.0 {
+        inside = clamp(0.5 - distance - in.border_width, 0.0, 1.0);
+    }
+
+    // Blend the border into the fill with premultiplied colors, so a transparent fill doesn't
+    // darken the inside of the border.
+    let border = vec4<f32>(in.border_color.rgb * in.border_color.a, in.border_color.a);
+    let fill = vec4<f32>(in.color.rgb * in.color.a, in.color.a);
+    let color = mix(border, fill, inside);
+    if color.a <= 0.0 {
+        discard;
+    }
+    return vec4<f32>(color.rgb / color.a, color.a * coverage);
+}
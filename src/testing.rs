@@ -0,0 +1,174 @@
+use std::path::{Path, PathBuf};
+
+use thiserror::Error;
+
+use crate::{
+	headless::{self, HeadlessRenderer, Image},
+	text::Font,
+	view::PhysicalSize,
+	widget::Widget,
+};
+
+pub type Result<T> = std::result::Result<T, Error>;
+
+/// Setting this environment variable to anything but `0` overwrites the reference images with the
+/// rendered ones instead of comparing them.
+pub const BLESS_VAR: &str = "KITSUNE_BLESS";
+
+#[derive(Debug, Error)]
+pub enum Error {
+	#[error("Could not render the widget: `{0}`")]
+	Headless(#[from] headless::Error),
+
+	#[error("Could not access a snapshot file: `{0}`")]
+	Io(#[from] std::io::Error),
+
+	#[error(
+		"There is no reference image at `{0}`, run with `{BLESS_VAR}=1` to create it from the rendered image"
+	)]
+	MissingReference(PathBuf),
+
+	#[error("The reference image is {expected:?}, but the widget was rendered at {actual:?}")]
+	SizeMismatch {
+		expected: PhysicalSize<u32>,
+		actual: PhysicalSize<u32>,
+	},
+
+	#[error(
+		"{pixels} pixels differ from the reference image, the differences are drawn in `{diff}`"
+	)]
+	Mismatch { pixels: usize, diff: PathBuf },
+}
+
+/// Compares rendered widgets with reference images stored in a directory.
+pub struct Snapshots {
+	renderer: HeadlessRenderer,
+	directory: PathBuf,
+	tolerance: u8,
+}
+
+impl Snapshots {
+	/// `tolerance` is the largest difference allowed in any channel of a pixel before the pixel
+	/// counts as different.
+	pub async fn new(directory: impl Into<PathBuf>, tolerance: u8) -> Result<Self> {
+		// A software adapter renders the same on every machine, but not every machine has one.
+		let renderer = match HeadlessRenderer::new(true).await {
+			Err(headless::Error::AdapterNotFound) => HeadlessRenderer::new(false).await?,
+			renderer => renderer?,
+		};
+
+		Ok(Self {
+			renderer,
+			directory: directory.into(),
+			tolerance,
+		})
+	}
+
+	pub fn with_font(mut self, font: Font) -> Self {
+		self.renderer.set_font(font);
+		self
+	}
+
+	/// Render `widget` and compare it to the reference image called `name`.
+	pub fn compare<T: Widget>(
+		&self,
+		name: &str,
+		widget: &mut T,
+		size: PhysicalSize<u32>,
+	) -> Result<()> {
+		let actual = self
+			.renderer
+			.render(widget, size)?;
+
+		let reference = self.path(name, "png");
+		let actual_path = self.path(name, "actual.png");
+		let diff_path = self.path(name, "diff.png");
+
+		if blessing() {
+			std::fs::create_dir_all(&self.directory)?;
+			std::fs::write(reference, actual.to_png()?)?;
+			remove_if_exists(&actual_path)?;
+			remove_if_exists(&diff_path)?;
+			return Ok(());
+		}
+
+		if !reference.exists() {
+			std::fs::write(actual_path, actual.to_png()?)?;
+			return Err(Error::MissingReference(reference));
+		}
+
+		let expected = Image::from_png(&std::fs::read(&reference)?)?;
+		if expected.size() != actual.size() {
+			std::fs::write(actual_path, actual.to_png()?)?;
+			return Err(Error::SizeMismatch {
+				expected: expected.size(),
+				actual: actual.size(),
+			});
+		}
+
+		let (pixels, diff) = self.diff(&expected, &actual);
+		if pixels > 0 {
+			std::fs::write(actual_path, actual.to_png()?)?;
+			std::fs::write(&diff_path, diff.to_png()?)?;
+			return Err(Error::Mismatch {
+				pixels,
+				diff: diff_path,
+			});
+		}
+
+		remove_if_exists(&actual_path)?;
+		remove_if_exists(&diff_path)?;
+
+		Ok(())
+	}
+
+	/// Like [`Snapshots::compare`], but panics when the widget does not match the reference.
+	pub fn assert<T: Widget>(&self, name: &str, widget: &mut T, size: PhysicalSize<u32>) {
+		if let Err(error) = self.compare(name, widget, size) {
+			panic!("snapshot `{name}` failed: {error}");
+		}
+	}
+
+	fn path(&self, name: &str, extension: &str) -> PathBuf {
+		self.directory
+			.join(format!("{name}.{extension}"))
+	}
+
+	fn diff(&self, expected: &Image, actual: &Image) -> (usize, Image) {
+		let mut pixels = 0;
+
+		let data = expected
+			.data()
+			.chunks(4)
+			.zip(actual.data().chunks(4))
+			.flat_map(|(expected, actual)| {
+				let different = expected
+					.iter()
+					.zip(actual)
+					.any(|(a, b)| a.abs_diff(*b) > self.tolerance);
+
+				if different {
+					pixels += 1;
+					[255, 0, 0, 255]
+				} else {
+					let gray = (actual[0] as u32 + actual[1] as u32 + actual[2] as u32) / 3;
+					let faded = (gray / 4 + 192) as u8;
+					[faded, faded, faded, 255]
+				}
+			})
+			.collect();
+
+		(pixels, Image::new(actual.size(), data))
+	}
+}
+
+fn blessing() -> bool {
+	std::env::var_os(BLESS_VAR).is_some_and(|value| !value.is_empty() && value != "0")
+}
+
+fn remove_if_exists(path: &Path) -> std::io::Result<()> {
+	match std::fs::remove_file(path) {
+		Err(error) if error.kind() == std::io::ErrorKind::NotFound => Ok(()),
+		result => result,
+	}
+}
@@ -1,101 +1,842 @@
-use ab_glyph::{Font as Font2, FontRef, Rect};
+use std::{
+	cell::RefCell,
+	collections::{BTreeMap, HashMap},
+	ops::Range,
+	rc::Rc,
+	sync::atomic::{AtomicUsize, Ordering},
+};
 
-use crate::texture::Texture;
+use ab_glyph::{Font as Font2, FontRef, GlyphId, InvalidFont, Rect, ScaleFont};
+use ttf_parser::{
+	gpos::{PairAdjustment, PositioningSubtable},
+	Tag,
+};
+use unicode_linebreak::{linebreaks, BreakOpportunity};
 
+use crate::view::PhysicalSize;
+
+/// How many horizontal offsets within a pixel glyphs are rasterized at, glyphs are placed at the
+/// nearest of these.
+const SUBPIXEL_STEPS: f32 = 4.0;
+
+const PARAGRAPH_CACHE_SIZE: usize = 256;
+
+/// A typeface, and the typefaces that draw the characters it doesn't have.
 pub struct Font {
+	faces: Vec<Face>,
+	paragraphs: RefCell<ParagraphCache>,
+}
+
+#[derive(Default)]
+struct ParagraphCache {
+	entries: HashMap<String, HashMap<ParagraphKey, (Rc<Paragraph>, u64)>>,
+	recent: BTreeMap<u64, (String, ParagraphKey)>,
+	uses: u64,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+struct ParagraphKey {
+	/// The bits of the size, which is a float.
+	size: u32,
+	width: Option<u32>,
+	wrap: Wrap,
+	max_lines: Option<usize>,
+}
+
+struct Face {
 	font: FontRef<'static>,
+	/// The pair adjustments of the `kern` feature, which most fonts kern with instead of a
+	/// `kern` table.
+	kerning: Vec<PairAdjustment<'static>>,
+	#[cfg(feature = "shaping")]
+	face: rustybuzz::Face<'static>,
+	id: usize,
+}
+
+impl Face {
+	fn new(data: &'static [u8]) -> Result<Self, InvalidFont> {
+		static NEXT_ID: AtomicUsize = AtomicUsize::new(0);
+
+		let font = FontRef::try_from_slice(data)?;
+		let face = ttf_parser::Face::parse(data, 0).map_err(|_| InvalidFont)?;
+		let kerning = face
+			.tables()
+			.gpos
+			.map(|gpos| {
+				// Every script has its own `kern` feature, which usually share their lookups.
+				let mut lookups = gpos
+					.features
+					.into_iter()
+					.filter(|x| x.tag == Tag::from_bytes(b"kern"))
+					.flat_map(|x| x.lookup_indices)
+					.collect::<Vec<_>>();
+				lookups.sort_unstable();
+				lookups.dedup();
+
+				lookups
+					.into_iter()
+					.filter_map(|x| gpos.lookups.get(x))
+					.flat_map(|x| {
+						x.subtables
+							.into_iter::<PositioningSubtable>()
+					})
+					.filter_map(|x| match x {
+						PositioningSubtable::Pair(pair) => Some(pair),
+						_ => None,
+					})
+					.collect()
+			})
+			.unwrap_or_default();
+
+		Ok(Self {
+			font,
+			kerning,
+			#[cfg(feature = "shaping")]
+			face: rustybuzz::Face::from_slice(data, 0).ok_or(InvalidFont)?,
+			id: NEXT_ID.fetch_add(1, Ordering::Relaxed),
+		})
+	}
+
+	fn has(&self, value: char) -> bool {
+		self.font.glyph_id(value).0 != 0
+	}
 }
 
 impl Font {
-	pub fn new(font: FontRef<'static>) -> Self {
-		Self { font }
+	/// The font in the TrueType or OpenType file `data`.
+	pub fn new(data: &'static [u8]) -> Result<Self, InvalidFont> {
+		Ok(Self {
+			faces: vec![Face::new(data)?],
+			paragraphs: Default::default(),
+		})
 	}
 
-	pub fn glyph(&self, value: char) -> Glyph {
-		let glyph = self
-			.font
-			.glyph_id(value)
-			.with_scale(100.0);
-		let size = self.font.glyph_bounds(&glyph);
-		Glyph { glyph, size }
+	/// The font that is bundled with this crate, and used by default.
+	pub fn roboto() -> Result<Self, InvalidFont> {
+		Self::new(include_bytes!("../res/Roboto/Roboto-Medium.ttf"))
 	}
 
-	pub fn rasterize(
+	/// This font with the characters it doesn't have drawn by `fallback`, or by the fallbacks of
+	/// `fallback`. Fallbacks are tried in the order they are added.
+	pub fn with_fallback(mut self, fallback: Font) -> Self {
+		self.faces.extend(fallback.faces);
+		self.paragraphs = Default::default();
+		self
+	}
+
+	/// `text` broken into lines, see [`Paragraph::new`].
+	pub fn paragraph(
 		&self,
-		glyph: Glyph,
-		device: &wgpu::Device,
-		format: wgpu::TextureFormat,
-		queue: &wgpu::Queue,
-		sampler: &wgpu::Sampler,
-		layout: &wgpu::BindGroupLayout,
-	) -> Option<wgpu::BindGroup> {
-		let size = glyph.size();
-
-		let outlined_glyph = self
-			.font
-			.outline_glyph(glyph.glyph)?;
-
-		let width = if size.min.x < 0.0 {
-			size.max.x - size.min.x
-		} else {
-			size.max.x
-		} as u32;
-		let height = if size.min.y < 0.0 {
-			size.max.y - size.min.y
-		} else {
-			size.max.y
-		} as u32;
-
-		let size = wgpu::Extent3d {
+		text: &str,
+		size: f32,
+		width: Option<u32>,
+		wrap: Wrap,
+		max_lines: Option<usize>,
+	) -> Rc<Paragraph> {
+		let key = ParagraphKey {
+			size: size.to_bits(),
 			width,
-			height,
-			depth_or_array_layers: 1,
+			wrap,
+			max_lines,
 		};
 
-		let mut texture = Texture::new(device, size, format);
+		let mut cache = self.paragraphs.borrow_mut();
+		let cache = &mut *cache;
+		cache.uses += 1;
+		let uses = cache.uses;
+		if let Some((paragraph, last_used)) = cache
+			.entries
+			.get_mut(text)
+			.and_then(|x| x.get_mut(&key))
+		{
+			if let Some(entry) = cache.recent.remove(last_used) {
+				cache.recent.insert(uses, entry);
+			}
+			*last_used = uses;
+			return paragraph.clone();
+		}
+
+		if cache.recent.len() >= PARAGRAPH_CACHE_SIZE {
+			if let Some((_, (text, key))) = cache.recent.pop_first() {
+				if let Some(paragraphs) = cache.entries.get_mut(&text) {
+					paragraphs.remove(&key);
+					if paragraphs.is_empty() {
+						cache.entries.remove(&text);
+					}
+				}
+			}
+		}
+
+		let paragraph = Rc::new(Paragraph::new(self, text, size, width, wrap, max_lines));
+		cache
+			.entries
+			.entry(text.to_string())
+			.or_default()
+			.insert(key, (paragraph.clone(), uses));
+		cache
+			.recent
+			.insert(uses, (text.to_string(), key));
+		paragraph
+	}
+
+	fn face_of(&self, value: char) -> usize {
+		self.faces
+			.iter()
+			.position(|x| x.has(value))
+			.unwrap_or(0)
+	}
+
+	/// How lines of text that are `size` pixels high are spaced, which only depends on the first
+	/// face so lines are equally high whichever faces their characters are drawn with.
+	pub fn line_metrics(&self, size: f32) -> LineMetrics {
+		let font = self.faces[0].font.as_scaled(size);
+		LineMetrics {
+			ascent: font.ascent(),
+			descent: font.descent(),
+			line_gap: font.line_gap(),
+		}
+	}
+
+	/// The glyph of `value` in a line of text that is `size` pixels high.
+	pub fn glyph(&self, value: char, size: f32) -> Glyph {
+		let face = self.face_of(value);
+		self.glyph_by_id(
+			face,
+			self.faces[face]
+				.font
+				.glyph_id(value),
+			size,
+		)
+	}
+
+	fn glyph_by_id(&self, face: usize, id: GlyphId, size: f32) -> Glyph {
+		let font = self.faces[face]
+			.font
+			.as_scaled(size);
+		Glyph {
+			glyph: id.with_scale(size),
+			face,
+			advance: font.h_advance(id),
+			side_bearing: font.h_side_bearing(id),
+			line: self.line_metrics(size),
+		}
+	}
+
+	/// The space added between `first` and `second` when `second` follows it, in pixels. This
+	/// is negative for pairs that are moved closer together, like `AV`. Glyphs of different
+	/// faces are never kerned.
+	pub fn kern(&self, first: &Glyph, second: &Glyph) -> f32 {
+		if first.face != second.face {
+			return 0.0;
+		}
+
+		let face = &self.faces[first.face];
+		let font = face
+			.font
+			.as_scaled(first.glyph.scale);
+		let ids = (first.glyph.id, second.glyph.id);
+		let (first, second) = (ttf_parser::GlyphId(ids.0 .0), ttf_parser::GlyphId(ids.1 .0));
+
+		// The first subtable that covers the pair decides its kerning.
+		let adjustment = face
+			.kerning
+			.iter()
+			.find_map(|x| match x {
+				PairAdjustment::Format1 { coverage, sets } => {
+					let (record, _) = sets
+						.get(coverage.get(first)?)?
+						.get(second)?;
+					Some(record.x_advance)
+				}
+				PairAdjustment::Format2 {
+					coverage,
+					classes,
+					matrix,
+				} => {
+					coverage.get(first)?;
+					let (record, _) = matrix.get((classes.0.get(first), classes.1.get(second)))?;
+					Some(record.x_advance)
+				}
+			});
+
+		match adjustment {
+			Some(adjustment) => font.h_scale_factor() * adjustment as f32,
+			None => font.kern(ids.0, ids.1),
+		}
+	}
+
+	/// The glyphs of a line of `text` that is `size` pixels high, in runs from left to right.
+	#[cfg(not(feature = "shaping"))]
+	pub fn shape(&self, text: &str, size: f32) -> Vec<GlyphRun> {
+		let mut glyphs = text
+			.char_indices()
+			.map(|(cluster, value)| {
+				let glyph = self.glyph(value, size);
+				ShapedGlyph {
+					advance: glyph.advance(),
+					glyph,
+					cluster,
+					offset: (0.0, 0.0),
+				}
+			})
+			.collect::<Vec<_>>();
+		for index in 1..glyphs.len() {
+			glyphs[index - 1].advance += self.kern(&glyphs[index - 1].glyph, &glyphs[index].glyph);
+		}
 
-		let mut data = vec![0; (4 * size.width * size.height) as usize];
+		if glyphs.is_empty() {
+			return vec![];
+		}
+		vec![GlyphRun {
+			range: 0..text.len(),
+			rtl: false,
+			glyphs,
+		}]
+	}
+
+	/// The glyphs of a line of `text` that is `size` pixels high, in runs from left to right.
+	#[cfg(feature = "shaping")]
+	pub fn shape(&self, text: &str, size: f32) -> Vec<GlyphRun> {
+		use unicode_bidi::BidiInfo;
+
+		let bidi = BidiInfo::new(text, None);
 
-		let mut max_y = 0;
+		let mut runs = vec![];
+		for paragraph in &bidi.paragraphs {
+			let (levels, visual) = bidi.visual_runs(paragraph, paragraph.range.clone());
+			for range in visual {
+				let rtl = levels[range.start].is_rtl();
 
-		// TODO: I do not know how to correctly do the offset of characters otherwise.
-		outlined_glyph.draw(|_, y, _| {
-			if y > max_y {
-				max_y = y
+				// Characters stay in the face of the characters before them when it has them, so
+				// spaces and marks are shaped along with the words they are in.
+				let mut parts: Vec<(usize, Range<usize>)> = vec![];
+				for (index, value) in text[range.clone()].char_indices() {
+					let start = range.start + index;
+					let end = start + value.len_utf8();
+					match parts.last_mut() {
+						Some((face, part)) if self.faces[*face].has(value) => part.end = end,
+						_ => parts.push((self.face_of(value), start..end)),
+					}
+				}
+				if rtl {
+					parts.reverse();
+				}
+
+				for (face, range) in parts {
+					runs.push(self.shape_part(text, range, rtl, face, size));
+				}
 			}
+		}
+		runs
+	}
+
+	#[cfg(feature = "shaping")]
+	fn shape_part(
+		&self,
+		text: &str,
+		range: Range<usize>,
+		rtl: bool,
+		face: usize,
+		size: f32,
+	) -> GlyphRun {
+		use rustybuzz::{Direction, UnicodeBuffer};
+
+		let scale = self.faces[face]
+			.font
+			.as_scaled(size)
+			.h_scale_factor();
+		let mut buffer = UnicodeBuffer::new();
+		buffer.push_str(&text[range.clone()]);
+		buffer.set_direction(match rtl {
+			true => Direction::RightToLeft,
+			false => Direction::LeftToRight,
 		});
+		buffer.guess_segment_properties();
 
-		let y_offset = (size.height - 1) - max_y;
+		let output = rustybuzz::shape(&self.faces[face].face, &[], buffer);
+		let glyphs = output
+			.glyph_infos()
+			.iter()
+			.zip(output.glyph_positions())
+			.map(|(info, position)| ShapedGlyph {
+				glyph: self.glyph_by_id(face, GlyphId(info.glyph_id as u16), size),
+				cluster: range.start + info.cluster as usize,
+				advance: position.x_advance as f32 * scale,
+				offset: (
+					position.x_offset as f32 * scale,
+					-position.y_offset as f32 * scale,
+				),
+			})
+			.collect();
+		GlyphRun { range, rtl, glyphs }
+	}
 
-		outlined_glyph.draw(|x, mut y, c| {
-			let color_value = 0;
-			let alpha_value = (255.0 * c) as u8;
+	/// What makes the rasterized `glyph` different from that of any other glyph.
+	pub fn key(&self, glyph: &Glyph) -> GlyphKey {
+		let offset = glyph
+			.glyph
+			.position
+			.x
+			.rem_euclid(1.0);
+		GlyphKey {
+			face: self.faces[glyph.face].id,
+			id: glyph.glyph.id.0,
+			scale: glyph.glyph.scale.y.to_bits(),
+			subpixel: (offset * SUBPIXEL_STEPS).round() as u8 % SUBPIXEL_STEPS as u8,
+		}
+	}
 
-			y += y_offset;
+	/// The pixels the outline of `glyph` covers, relative to where it starts on the baseline.
+	/// Returns `None` for glyphs without an outline, like spaces.
+	pub fn bounds(&self, glyph: &Glyph) -> Option<Rect> {
+		let bounds = self.faces[glyph.face]
+			.font
+			.outline_glyph(self.positioned(glyph))?
+			.px_bounds();
+		Some(bounds)
+	}
 
-			let index = size.width * y + x;
-			let index = index as usize * 4;
+	/// The pixels of `glyph` in white with the coverage as alpha, 4 bytes per pixel, and how
+	/// large the image is. The image covers the `bounds` of the glyph.
+	pub fn rasterize(&self, glyph: &Glyph) -> Option<(PhysicalSize<u32>, Vec<u8>)> {
+		let outlined_glyph = self.faces[glyph.face]
+			.font
+			.outline_glyph(self.positioned(glyph))?;
+		let bounds = outlined_glyph.px_bounds();
+		let width = bounds.width() as u32;
+		let height = bounds.height() as u32;
 
-			data[index] = color_value;
-			data[index + 1] = color_value;
-			data[index + 2] = color_value;
-			data[index + 3] = alpha_value;
+		let mut data = vec![0; (4 * width * height) as usize];
+		outlined_glyph.draw(|x, y, c| {
+			if x >= width || y >= height {
+				return;
+			}
+
+			// Glyphs are white, so they take on the color of the vertices they are drawn with.
+			let index = (width * y + x) as usize * 4;
+			data[index..index + 3].fill(255);
+			data[index + 3] = (255.0 * c) as u8;
 		});
 
-		texture.write_data(queue, &data);
+		Some((PhysicalSize::new(width, height), data))
+	}
+
+	fn positioned(&self, glyph: &Glyph) -> ab_glyph::Glyph {
+		let key = self.key(glyph);
+		let mut positioned = glyph.glyph.clone();
+		positioned.position = ab_glyph::point(key.subpixel as f32 / SUBPIXEL_STEPS, 0.0);
+		positioned
+	}
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct LineMetrics {
+	/// How far the font reaches above the baseline.
+	pub ascent: f32,
+	/// How far the font reaches below the baseline, this is negative.
+	pub descent: f32,
+	/// The space the font asks for between the descent of a line and the ascent of the next.
+	pub line_gap: f32,
+}
+
+impl LineMetrics {
+	/// How high a line is, the line gap is split above and below it.
+	pub fn height(&self) -> f32 {
+		self.ascent - self.descent + self.line_gap
+	}
 
-		Some(texture.bind_group(device, layout, sampler))
+	/// The distance from the top of a line to its baseline.
+	pub fn baseline(&self) -> f32 {
+		self.line_gap / 2.0 + self.ascent
 	}
 }
 
+/// Identifies a rasterized glyph: its face, its id in that face, its size and the offset within
+/// a pixel it was rasterized at.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct GlyphKey {
+	face: usize,
+	id: u16,
+	/// The bits of the scale, which is a float.
+	scale: u32,
+	subpixel: u8,
+}
+
+/// A character in a font at a certain size, and how it is spaced on its line.
 #[derive(Debug, Clone)]
 pub struct Glyph {
 	glyph: ab_glyph::Glyph,
-	size: Rect,
+	face: usize,
+	advance: f32,
+	side_bearing: f32,
+	line: LineMetrics,
 }
 
 impl Glyph {
-	pub fn size(&self) -> Rect {
-		self.size
+	/// How far the next glyph on the line starts after this one.
+	pub fn advance(&self) -> f32 {
+		self.advance
+	}
+
+	/// The space between where this glyph starts and the left of its outline.
+	pub fn side_bearing(&self) -> f32 {
+		self.side_bearing
+	}
+
+	pub fn line_metrics(&self) -> LineMetrics {
+		self.line
+	}
+
+	/// The space this glyph takes up in a line of text, rounded to whole pixels.
+	pub fn size(&self) -> PhysicalSize<u32> {
+		PhysicalSize::new(
+			self.advance.round().max(0.0) as u32,
+			self.line.height().round() as u32,
+		)
 	}
+
+	/// The distance from the top of the line to its baseline, rounded to whole pixels.
+	pub fn baseline(&self) -> u32 {
+		self.line.baseline().round() as u32
+	}
+
+	/// This glyph starting `x` pixels to the right of a pixel, only the part of `x` within a
+	/// pixel changes how it is rasterized.
+	pub fn with_offset(mut self, x: f32) -> Self {
+		self.glyph.position.x = x;
+		self
+	}
+}
+
+#[derive(Debug, Clone)]
+pub struct ShapedGlyph {
+	pub glyph: Glyph,
+	/// The byte offset in the text of the first character this glyph shows. Glyphs that show the
+	/// same characters, like a letter and its accent, share their cluster.
+	pub cluster: usize,
+	/// How far the next glyph starts after this one, which includes kerning.
+	pub advance: f32,
+	/// How far the glyph is moved to the right and down from where it starts.
+	pub offset: (f32, f32),
+}
+
+/// A part of a line of text that goes in one direction, with its glyphs from left to right.
+#[derive(Debug, Clone)]
+pub struct GlyphRun {
+	/// The bytes of the text this run shows.
+	pub range: Range<usize>,
+	/// Whether the text of this run is read from right to left, its glyphs are still stored
+	/// from left to right.
+	pub rtl: bool,
+	pub glyphs: Vec<ShapedGlyph>,
+}
+
+impl GlyphRun {
+	pub fn advance(&self) -> f32 {
+		self.glyphs
+			.iter()
+			.map(|x| x.advance)
+			.sum()
+	}
+}
+
+const ELLIPSIS: char = '\u{2026}';
+
+/// How the lines of a paragraph are broken when they don't fit.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum Wrap {
+	/// Break lines between words, where the unicode line breaking algorithm allows it. Words that
+	/// don't fit on a line of their own are broken between characters.
+	#[default]
+	Word,
+	/// Break lines before the character that doesn't fit.
+	Character,
+	/// Only break lines at newlines, and cut lines that don't fit short with an ellipsis.
+	None,
+}
+
+/// Text broken into lines of glyphs.
+#[derive(Debug, Clone)]
+pub struct Paragraph {
+	lines: Vec<Line>,
+	metrics: LineMetrics,
+}
+
+#[derive(Debug, Clone)]
+pub struct Line {
+	/// The bytes of the text on this line, including the newline that ends it.
+	pub range: Range<usize>,
+	/// The glyphs of this line in runs from left to right, as returned by `Font::shape`.
+	pub runs: Vec<GlyphRun>,
+	/// The left and right of every character on this line, in the order of the text.
+	pub characters: Vec<(f32, f32)>,
+}
+
+impl Paragraph {
+	/// `text` in lines that are `size` pixels high, which are broken by `wrap` when they are
+	/// wider than `width` pixels, and at every newline.
+	pub fn new(
+		font: &Font,
+		text: &str,
+		size: f32,
+		width: Option<u32>,
+		wrap: Wrap,
+		max_lines: Option<usize>,
+	) -> Self {
+		let mut ranges = vec![];
+		let mut allowed = vec![];
+		let mut start = 0;
+		for (index, opportunity) in linebreaks(text).filter(|_| !text.is_empty()) {
+			if opportunity == BreakOpportunity::Allowed {
+				allowed.push(index);
+				continue;
+			}
+
+			let content = without_newline(text, start..index);
+			let breaks = match (wrap, width) {
+				(Wrap::Word | Wrap::Character, Some(width)) => {
+					let allowed = match wrap {
+						Wrap::Word => &allowed[..],
+						_ => &[],
+					};
+					wrap_line(font, text, content, size, width, allowed)
+				}
+				_ => vec![],
+			};
+
+			let starts = [start]
+				.into_iter()
+				.chain(breaks.iter().copied());
+			let ends = breaks
+				.iter()
+				.copied()
+				.chain([index]);
+			ranges.extend(
+				starts
+					.zip(ends)
+					.map(|(start, end)| (start..end, false)),
+			);
+
+			allowed.clear();
+			start = index;
+		}
+
+		if let Some(max_lines) = max_lines.filter(|x| ranges.len() > *x) {
+			ranges.truncate(max_lines);
+			if let Some((_, ellipsis)) = ranges.last_mut() {
+				*ellipsis = true;
+			}
+		}
+
+		let lines = ranges
+			.into_iter()
+			.map(|(range, ellipsis)| {
+				let content = without_newline(text, range.clone());
+				let cut = ellipsis
+					|| (wrap == Wrap::None
+						&& width.is_some_and(|x| {
+							measure(font, &text[content.clone()], size).round() > x as f32
+						}));
+				let end = match cut {
+					true => cut_short(font, text, content, size, width),
+					false => content.end,
+				};
+				Line::new(font, text, range, end, cut, size)
+			})
+			.collect();
+
+		Self {
+			lines,
+			metrics: font.line_metrics(size),
+		}
+	}
+
+	pub fn lines(&self) -> &[Line] {
+		&self.lines
+	}
+
+	/// How high every line is, rounded to whole pixels.
+	pub fn line_height(&self) -> u32 {
+		self.metrics.height().round() as u32
+	}
+
+	/// The distance from the top of a line to its baseline, rounded to whole pixels.
+	pub fn baseline(&self) -> u32 {
+		self.metrics.baseline().round() as u32
+	}
+
+	/// The width of the widest line, rounded to whole pixels.
+	pub fn width(&self) -> u32 {
+		self.lines
+			.iter()
+			.map(|x| x.width().round() as u32)
+			.max()
+			.unwrap_or(0)
+	}
+
+	pub fn height(&self) -> u32 {
+		self.lines.len() as u32 * self.line_height()
+	}
+}
+
+impl Line {
+	fn new(
+		font: &Font,
+		text: &str,
+		range: Range<usize>,
+		end: usize,
+		ellipsis: bool,
+		size: f32,
+	) -> Self {
+		let mut shown = text[range.start..end].to_string();
+		if ellipsis {
+			shown.push(ELLIPSIS);
+		}
+
+		let mut runs = font.shape(&shown, size);
+		for run in &mut runs {
+			run.range = run.range.start + range.start..run.range.end + range.start;
+			for glyph in &mut run.glyphs {
+				glyph.cluster += range.start;
+			}
+		}
+
+		// The left, right and direction of every cluster.
+		let mut spans: Vec<(usize, f32, f32, bool)> = vec![];
+		let mut x = 0.0;
+		for run in &runs {
+			for glyph in &run.glyphs {
+				match spans.last_mut() {
+					Some(span) if span.0 == glyph.cluster => span.2 += glyph.advance,
+					_ => spans.push((glyph.cluster, x, x + glyph.advance, run.rtl)),
+				}
+				x += glyph.advance;
+			}
+		}
+		// The ellipsis is not a part of the text.
+		spans.retain(|x| x.0 < end);
+		spans.sort_by_key(|x| x.0);
+
+		let mut characters = vec![];
+		for (index, &(cluster, left, right, rtl)) in spans.iter().enumerate() {
+			let next = spans
+				.get(index + 1)
+				.map_or(end, |x| x.0);
+			let count = text[cluster..next].chars().count();
+			let part = (right - left) / count as f32;
+			characters.extend((0..count).map(|i| {
+				let i = i as f32;
+				match rtl {
+					true => (right - (i + 1.0) * part, right - i * part),
+					false => (left + i * part, left + (i + 1.0) * part),
+				}
+			}));
+		}
+		characters.extend(
+			text[end..range.end]
+				.chars()
+				.map(|_| (x, x)),
+		);
+
+		Self {
+			range,
+			runs,
+			characters,
+		}
+	}
+
+	pub fn width(&self) -> f32 {
+		self.runs
+			.iter()
+			.map(|x| x.advance())
+			.sum()
+	}
+}
+
+fn is_newline(value: char) -> bool {
+	matches!(
+		value,
+		'\n' | '\r' | '\u{0b}' | '\u{0c}' | '\u{85}' | '\u{2028}' | '\u{2029}'
+	)
+}
+
+fn without_newline(text: &str, range: Range<usize>) -> Range<usize> {
+	let content = text[range.clone()].trim_end_matches(is_newline);
+	range.start..range.start + content.len()
+}
+
+fn clusters(font: &Font, text: &str, range: Range<usize>, size: f32) -> Vec<(usize, f32)> {
+	let mut clusters = BTreeMap::new();
+	for glyph in font
+		.shape(&text[range.clone()], size)
+		.iter()
+		.flat_map(|x| &x.glyphs)
+	{
+		*clusters
+			.entry(range.start + glyph.cluster)
+			.or_insert(0.0) += glyph.advance;
+	}
+	clusters.into_iter().collect()
+}
+
+fn measure(font: &Font, text: &str, size: f32) -> f32 {
+	font.shape(text, size)
+		.iter()
+		.map(|x| x.advance())
+		.sum()
+}
+
+fn wrap_line(
+	font: &Font,
+	text: &str,
+	range: Range<usize>,
+	size: f32,
+	width: u32,
+	allowed: &[usize],
+) -> Vec<usize> {
+	let clusters = clusters(font, text, range, size);
+	let mut prefix = vec![0.0];
+	for (_, advance) in &clusters {
+		prefix.push(prefix[prefix.len() - 1] + advance);
+	}
+	let fits = |from: usize, to: usize| (prefix[to] - prefix[from]).round() <= width as f32;
+
+	let mut breaks = vec![];
+	let mut start = 0;
+	for (index, (cluster, _)) in clusters.iter().enumerate() {
+		// Spaces are allowed to hang past the end of a line, so the line is broken after them.
+		if text[*cluster..].starts_with(char::is_whitespace) {
+			continue;
+		}
+
+		while index > start && !fits(start, index + 1) {
+			start = (start + 1..=index)
+				.rev()
+				.find(|&x| allowed.contains(&clusters[x].0))
+				.unwrap_or(index);
+			breaks.push(clusters[start].0);
+		}
+	}
+	breaks
+}
+
+fn cut_short(font: &Font, text: &str, range: Range<usize>, size: f32, width: Option<u32>) -> usize {
+	let available =
+		width.map_or(f32::INFINITY, |x| x as f32) - measure(font, &ELLIPSIS.to_string(), size);
+
+	let mut end = range.end;
+	let mut line_width = 0.0;
+	for (cluster, advance) in clusters(font, text, range.clone(), size) {
+		if (line_width + advance).round() > available {
+			end = cluster;
+			break;
+		}
+		line_width += advance;
+	}
+
+	// There is no need for spaces before the ellipsis.
+	range.start
+		+ text[range.start..end]
+			.trim_end()
+			.len()
 }
@@ -1,3 +1,5 @@
+use crate::view::{PhysicalPosition, PhysicalSize};
+
 pub struct Texture {
 	size: wgpu::Extent3d,
 	texture: wgpu::Texture,
@@ -13,7 +15,9 @@ impl Texture {
 			sample_count: 1,
 			dimension: wgpu::TextureDimension::D2,
 			format,
-			usage: wgpu::TextureUsages::COPY_DST | wgpu::TextureUsages::TEXTURE_BINDING,
+			usage: wgpu::TextureUsages::COPY_DST
+				| wgpu::TextureUsages::COPY_SRC
+				| wgpu::TextureUsages::TEXTURE_BINDING,
 			view_formats: &[],
 		});
 
@@ -44,6 +48,48 @@ impl Texture {
 		)
 	}
 
+	/// Write `data` to the part of the texture of `size` with its top-left at `origin`.
+	pub fn write_region(
+		&mut self,
+		queue: &wgpu::Queue,
+		origin: PhysicalPosition<u32>,
+		size: PhysicalSize<u32>,
+		data: &[u8],
+	) {
+		queue.write_texture(
+			wgpu::ImageCopyTexture {
+				texture: &self.texture,
+				mip_level: 0,
+				origin: wgpu::Origin3d {
+					x: origin.x,
+					y: origin.y,
+					z: 0,
+				},
+				aspect: wgpu::TextureAspect::All,
+			},
+			data,
+			wgpu::ImageDataLayout {
+				offset: 0,
+				bytes_per_row: Some(4 * size.width),
+				rows_per_image: Some(size.height),
+			},
+			wgpu::Extent3d {
+				width: size.width,
+				height: size.height,
+				depth_or_array_layers: 1,
+			},
+		)
+	}
+
+	/// Copy all of this texture to the top-left of `target`, which has to be at least as large.
+	pub fn copy_to(&self, encoder: &mut wgpu::CommandEncoder, target: &Texture) {
+		encoder.copy_texture_to_texture(
+			self.texture.as_image_copy(),
+			target.texture.as_image_copy(),
+			self.size,
+		);
+	}
+
 	pub fn bind_group(
 		&self,
 		device: &wgpu::Device,
@@ -1,15 +1,17 @@
-use winit::dpi::{PhysicalPosition, PhysicalSize};
+use crate::{layout::Justify, render::Vertex};
 
-use crate::render::Vertex;
-
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, PartialEq, Copy)]
 pub struct GlobalView {
 	size: PhysicalSize<u32>,
+	scroll: PhysicalPosition<u32>,
 }
 
 impl GlobalView {
 	pub fn new(size: PhysicalSize<u32>) -> Self {
-		Self { size }
+		Self {
+			size,
+			scroll: PhysicalPosition::new(0, 0),
+		}
 	}
 
 	pub fn view(&self, size: PhysicalSize<u32>, offset: PhysicalPosition<u32>) -> View {
@@ -21,7 +23,7 @@ impl GlobalView {
 	}
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct View {
 	global: GlobalView,
 	size: PhysicalSize<u32>,
@@ -51,7 +53,7 @@ impl View {
 
 	pub fn physical_width_hint(&self, hint: SizeHint) -> Option<u32> {
 		match hint {
-			SizeHint::None => None,
+			SizeHint::None | SizeHint::Flex(_) => None,
 			SizeHint::Physical(value) => Some(value),
 			SizeHint::Virtual(value) => Some(self.physical_x(value)),
 			SizeHint::Max(value) => value
@@ -71,7 +73,7 @@ impl View {
 
 	pub fn physical_height_hint(&self, hint: SizeHint) -> Option<u32> {
 		match hint {
-			SizeHint::None => None,
+			SizeHint::None | SizeHint::Flex(_) => None,
 			SizeHint::Physical(value) => Some(value),
 			SizeHint::Virtual(value) => Some(self.physical_y(value)),
 			SizeHint::Max(value) => value
@@ -91,7 +93,7 @@ impl View {
 
 	pub fn virtualize_width_hint(&self, hint: SizeHint) -> Option<f32> {
 		match hint {
-			SizeHint::None => None,
+			SizeHint::None | SizeHint::Flex(_) => None,
 			SizeHint::Physical(value) => Some(self.virtualize_x(value)),
 			SizeHint::Virtual(value) => Some(value),
 			SizeHint::Max(value) => value
@@ -111,7 +113,7 @@ impl View {
 
 	pub fn virtualize_height_hint(&self, hint: SizeHint) -> Option<f32> {
 		match hint {
-			SizeHint::None => None,
+			SizeHint::None | SizeHint::Flex(_) => None,
 			SizeHint::Physical(value) => Some(self.virtualize_y(value)),
 			SizeHint::Virtual(value) => Some(value),
 			SizeHint::Max(value) => value
@@ -130,10 +132,18 @@ impl View {
 	}
 
 	pub fn globalize(&self, pos: VirtualPosition) -> GlobalPosition {
-		let x = ((pos.x * self.size.width as f32) + self.offset.x as f32)
-			/ self.global.size.width as f32;
-		let y = ((pos.y * self.size.height as f32) + self.offset.y as f32)
-			/ self.global.size.height as f32;
+		self.globalize_physical(
+			pos.x * self.size.width as f32,
+			pos.y * self.size.height as f32,
+		)
+	}
+
+	/// The global position of the point `x` pixels right of and `y` pixels below the top-left of
+	/// this view, which may be outside of it.
+	pub fn globalize_physical(&self, x: f32, y: f32) -> GlobalPosition {
+		let position = self.position();
+		let x = (x + position.x as f32) / self.global.size.width as f32;
+		let y = (y + position.y as f32) / self.global.size.height as f32;
 
 		// Wgpu uses a coordinate system where bottom-left is (-1.0, -1.0) and the top-right is
 		// (1.0, 1.0).
@@ -145,63 +155,232 @@ impl View {
 		GlobalPosition { x, y }
 	}
 
-	pub fn split_row(self, hints: Vec<SizeHint>) -> Vec<Self> {
-		let mut values = vec![];
-		let mut offset = 0;
+	fn resolve_hint(hint: &SizeHint, share: f32, physical: &impl Fn(f32) -> u32) -> Option<f32> {
+		match hint {
+			SizeHint::None => None,
+			SizeHint::Physical(value) => Some(*value as f32),
+			SizeHint::Virtual(value) => Some(physical(*value) as f32),
+			SizeHint::Flex(weight) => Some(weight * share),
+			SizeHint::Max(value) => value
+				.iter()
+				.flat_map(|x| Self::resolve_hint(x, share, physical))
+				.reduce(f32::max),
+			SizeHint::Min(value) => value
+				.iter()
+				.flat_map(|x| Self::resolve_hint(x, share, physical))
+				.reduce(f32::min),
+			// Unlike `Max` and `Min`, where a value without a size is simply ignored, a sum grows
+			// along with the values that fill the remaining space.
+			SizeHint::Sum(value) => value
+				.iter()
+				.map(|x| Self::resolve_hint(x, share, physical).unwrap_or(share))
+				.reduce(|a, b| a + b),
+		}
+	}
+
+	fn distribute(available: u32, hints: &[SizeHint], physical: impl Fn(f32) -> u32) -> Vec<u32> {
+		let resolve = |hint: &SizeHint, share: f32| {
+			Self::resolve_hint(hint, share, &physical).unwrap_or(share)
+		};
+		let total = |share: f32| {
+			hints
+				.iter()
+				.map(|x| resolve(x, share))
+				.sum::<f32>()
+		};
+
+		let sizes = hints
+			.iter()
+			.map(|x| resolve(x, 0.0))
+			.collect::<Vec<_>>();
+		let flexible = hints
+			.iter()
+			.zip(&sizes)
+			.map(|(x, size)| resolve(x, MAX_SHARE) > *size)
+			.collect::<Vec<_>>();
+
+		let available = available as f32;
+		let fixed = total(0.0);
+
+		if fixed >= available {
+			// Fixed sizes are shrunk first, and only when that is not enough the minimum sizes of
+			// the flexible hints are shrunk as well.
+			let total_of = |flexible_only: bool| {
+				sizes
+					.iter()
+					.zip(&flexible)
+					.filter(|(_, flexible)| **flexible == flexible_only)
+					.map(|(size, _)| size)
+					.sum::<f32>()
+			};
+			let scale = |total: f32, deficit: f32| {
+				if total > 0.0 {
+					((total - deficit) / total).clamp(0.0, 1.0)
+				} else {
+					1.0
+				}
+			};
+
+			let rigid = total_of(false);
+			let rigid_scale = scale(rigid, fixed - available);
+			let flexible_scale = scale(total_of(true), fixed - available - rigid);
+
+			return sizes
+				.into_iter()
+				.zip(flexible)
+				.map(|(size, flexible)| {
+					if flexible {
+						size * flexible_scale
+					} else {
+						size * rigid_scale
+					}
+				})
+				.map(|x| x as u32)
+				.collect();
+		}
+
+		if !flexible.contains(&true) {
+			return sizes
+				.into_iter()
+				.map(|x| x as u32)
+				.collect();
+		}
+
+		// The total size only grows with the share, so the share that fills all of the available
+		// space can be found by searching for it.
+		let mut low = 0.0;
+		let mut high = 1.0;
+		while total(high) < available && high < MAX_SHARE {
+			low = high;
+			high *= 2.0;
+		}
+		for _ in 0..32 {
+			let middle = (low + high) / 2.0;
+			if total(middle) < available {
+				low = middle;
+			} else {
+				high = middle;
+			}
+		}
+
+		let mut sizes = hints
+			.iter()
+			.map(|x| resolve(x, low) as u32)
+			.collect::<Vec<_>>();
+
+		// Rounding down can leave a few pixels, which go to the first flexible hints with room.
+		let mut leftover = (available as u32).saturating_sub(sizes.iter().sum());
+		for ((size, hint), flexible) in sizes
+			.iter_mut()
+			.zip(hints)
+			.zip(flexible)
+		{
+			if leftover > 0 && flexible && resolve(hint, high).ceil() as u32 > *size {
+				*size += 1;
+				leftover -= 1;
+			}
+		}
+
+		sizes
+	}
+
+	/// Split this view into values placed next to each other, `gap` pixels apart, with any space
+	/// that is left divided according to `justify`.
+	pub fn split_row(self, hints: Vec<SizeHint>, justify: Justify, gap: u32) -> Vec<Self> {
+		let gaps = gap * (hints.len() as u32).saturating_sub(1);
+		let available = self
+			.size
+			.width
+			.saturating_sub(gaps);
+		let widths = Self::distribute(available, &hints, |x| self.physical_x(x));
 
-		for hint in hints {
-			// TODO: I do not yet know how to handle an unknown size hint
-			let width = self
-				.physical_width_hint(hint)
-				.unwrap_or(0);
+		let free = available.saturating_sub(widths.iter().sum());
+		let (mut offset, spacing) = justify.spacing(free, widths.len());
 
+		let mut values = vec![];
+
+		for width in widths {
 			let size = PhysicalSize::new(width, self.size.height);
 			values.push(self.global.view(
 				size,
-				PhysicalPosition::new(self.offset.x + offset, self.offset.y),
+				PhysicalPosition::new(self.offset.x + offset as u32, self.offset.y),
 			));
 
-			offset += width;
+			offset += (width + gap) as f32 + spacing;
 		}
 		values
 	}
 
-	pub fn split_column(self, hints: Vec<SizeHint>) -> Vec<Self> {
-		let mut values = vec![];
-		let mut offset = 0;
+	/// Split this view into values placed below each other, `gap` pixels apart, with any space
+	/// that is left divided according to `justify`.
+	pub fn split_column(self, hints: Vec<SizeHint>, justify: Justify, gap: u32) -> Vec<Self> {
+		let gaps = gap * (hints.len() as u32).saturating_sub(1);
+		let available = self
+			.size
+			.height
+			.saturating_sub(gaps);
+		let heights = Self::distribute(available, &hints, |x| self.physical_y(x));
 
-		for hint in hints {
-			// TODO: I do not yet know how to handle an unknown size hint
-			let height = self
-				.physical_width_hint(hint)
-				.unwrap_or(0);
+		let free = available.saturating_sub(heights.iter().sum());
+		let (mut offset, spacing) = justify.spacing(free, heights.len());
+
+		let mut values = vec![];
 
+		for height in heights {
 			let size = PhysicalSize::new(self.size.width, height);
 			values.push(self.global.view(
 				size,
-				PhysicalPosition::new(self.offset.x, self.offset.y + offset),
+				PhysicalPosition::new(self.offset.x, self.offset.y + offset as u32),
 			));
 
-			offset += height;
+			offset += (height + gap) as f32 + spacing;
 		}
 		values
 	}
 
+	/// A view of `size` placed at `offset` relative to the top-left of this view.
+	pub fn sub_view(&self, offset: PhysicalPosition<u32>, size: PhysicalSize<u32>) -> View {
+		self.global.view(
+			size,
+			PhysicalPosition::new(self.offset.x + offset.x, self.offset.y + offset.y),
+		)
+	}
+
+	/// This view with the given number of pixels taken off of each side.
+	pub fn inset(&self, top: u32, right: u32, bottom: u32, left: u32) -> View {
+		let size = PhysicalSize::new(
+			self.size
+				.width
+				.saturating_sub(left + right),
+			self.size
+				.height
+				.saturating_sub(top + bottom),
+		);
+		self.sub_view(PhysicalPosition::new(left, top), size)
+	}
+
+	/// A view of `size` at the same offset as this one, whose contents are drawn `scroll` pixels
+	/// further up and to the left.
+	pub fn scrolled(&self, scroll: PhysicalPosition<u32>, size: PhysicalSize<u32>) -> View {
+		let global = GlobalView {
+			size: self.global.size,
+			scroll: PhysicalPosition::new(
+				self.global.scroll.x + scroll.x,
+				self.global.scroll.y + scroll.y,
+			),
+		};
+		global.view(size, self.offset)
+	}
+
 	pub fn bordered(self, width: u32) -> (Self, Self) {
-		let size = PhysicalSize::new(self.size.width - 2 * width, self.size.height - 2 * width);
-		let offset = PhysicalPosition::new(self.offset.x + width, self.offset.y + width);
-		let inner = self.global.view(size, offset);
+		let inner = self.inset(width, width, width, width);
 		(self, inner)
 	}
 
 	pub fn from_size_hints(self, width: SizeHint, height: SizeHint) -> View {
 		let size = PhysicalSize::new(
-			self.physical_width_hint(width)
-				.unwrap_or(self.size.width)
-				.min(self.width()),
-			self.physical_height_hint(height)
-				.unwrap_or(self.size.width)
-				.min(self.height()),
+			Self::distribute(self.size.width, &[width], |x| self.physical_x(x))[0],
+			Self::distribute(self.size.height, &[height], |x| self.physical_y(x))[0],
 		);
 		let offset = PhysicalPosition::new(self.offset.x, self.offset.y);
 		self.global.view(size, offset)
@@ -220,6 +399,23 @@ impl View {
 		]
 	}
 
+	pub fn size(&self) -> PhysicalSize<u32> {
+		self.size
+	}
+
+	pub fn offset(&self) -> PhysicalPosition<u32> {
+		self.offset
+	}
+
+	/// Where the top-left of this view is drawn, which is its offset minus how far it has been
+	/// scrolled.
+	pub fn position(&self) -> PhysicalPosition<i32> {
+		PhysicalPosition::new(
+			self.offset.x as i32 - self.global.scroll.x as i32,
+			self.offset.y as i32 - self.global.scroll.y as i32,
+		)
+	}
+
 	pub fn width(&self) -> u32 {
 		self.size.width
 	}
@@ -229,6 +425,32 @@ impl View {
 	}
 }
 
+/// A size in pixels on the screen.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct PhysicalSize<T> {
+	pub width: T,
+	pub height: T,
+}
+
+impl<T> PhysicalSize<T> {
+	pub const fn new(width: T, height: T) -> Self {
+		Self { width, height }
+	}
+}
+
+/// A position in pixels on the screen, from the top-left.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct PhysicalPosition<T> {
+	pub x: T,
+	pub y: T,
+}
+
+impl<T> PhysicalPosition<T> {
+	pub const fn new(x: T, y: T) -> Self {
+		Self { x, y }
+	}
+}
+
 #[derive(Debug, Clone, Copy)]
 pub struct VirtualPosition {
 	x: f32,
@@ -248,10 +470,15 @@ pub struct GlobalPosition {
 	y: f32,
 }
 
+const MAX_SHARE: f32 = 1e9;
+
+/// How large a widget would like to be along one axis.
+#[derive(Debug, Clone)]
 pub enum SizeHint {
 	None,
 	Physical(u32),
 	Virtual(f32),
+	Flex(f32),
 	Max(Vec<SizeHint>),
 	Min(Vec<SizeHint>),
 	Sum(Vec<SizeHint>),
@@ -1,32 +1,86 @@
-use itertools::Itertools;
-use winit::{dpi::PhysicalSize, event::WindowEvent};
-
 use crate::{
+	atlas::{AtlasGlyph, GlyphAtlas},
+	color::Color,
 	context::Context,
-	render::{Render, RenderedMesh},
-	text::Font,
-	view::{SizeHint, View},
+	event::{
+		route, ElementState, Event, Ime, Key, Modifiers, MouseButton, MouseScrollDelta,
+		PointerEvent, PointerKind, Propagation,
+	},
+	fill::Fill,
+	focus,
+	layout::{Align, Anchor, Justify, Layout, LayoutContext, Track},
+	render::{Clipped, Render, RenderedMesh, RenderedShape, Vertex},
+	shape::{BoxShadow, Radii, Shape},
+	text::{Glyph, Wrap},
+	view::{PhysicalPosition, PhysicalSize, SizeHint, View},
 };
 
 pub trait Widget {
 	type Renderable: Render;
 
+	/// Decide where this widget and all of its children are placed within `view`.
+	fn layout(&self, context: &Context<LayoutContext>, view: View) -> Layout;
+
+	/// Get ready to be drawn with `layout`, returns whether this changed the widget in a way that
+	/// needs it to be laid out again.
+	fn prepare(&mut self, layout: &Layout) -> bool {
+		let mut prepare = Prepare(false);
+		self.visit_children(layout, &mut prepare);
+		prepare.0
+	}
+
 	fn get_renderable(
 		&mut self,
 		context: &mut Context<WidgetContext>,
-		view: View,
+		layout: &Layout,
 	) -> Self::Renderable;
 
-	fn width_hint(&self, _context: &Context<WidgetContext>, _view: &View) -> SizeHint {
+	/// Whether this widget would be drawn differently than the last time `get_renderable` was
+	/// called, apart from changes to its layout.
+	fn changed(&mut self, layout: &Layout) -> bool {
+		let mut changed = Changed(false);
+		self.visit_children(layout, &mut changed);
+		changed.0
+	}
+
+	fn width_hint(&self, _context: &Context<LayoutContext>, _view: &View) -> SizeHint {
 		SizeHint::None
 	}
 
-	fn height_hint(&self, _context: &Context<WidgetContext>, _view: &View) -> SizeHint {
+	fn height_hint(&self, _context: &Context<LayoutContext>, _view: &View) -> SizeHint {
 		SizeHint::None
 	}
 
+	/// The distance from the top of this widget to the baseline of its text, if it has any.
+	fn baseline(&self, _context: &Context<LayoutContext>, _view: &View) -> Option<u32> {
+		None
+	}
+
 	fn resize(&mut self, _new_size: PhysicalSize<u32>) {}
-	fn handle(&mut self, _event: &WindowEvent) {}
+
+	/// Handle an event that is sent to every widget, pointer events are sent to
+	/// `handle_pointer` instead.
+	fn handle(&mut self, _event: &Event) {}
+
+	/// Handle a pointer event that happened over this widget, `layout` is where this widget was
+	/// the last time it was drawn.
+	fn handle_pointer(&mut self, _event: &PointerEvent, _layout: &Layout) -> Propagation {
+		Propagation::Continue
+	}
+
+	/// Whether this widget can take keyboard focus.
+	fn focusable(&self) -> bool {
+		false
+	}
+
+	/// Called when this widget gains or loses keyboard focus.
+	fn focus_changed(&mut self, _focused: bool) {}
+
+	/// Handle a keyboard event, these are only sent to the widget that has focus.
+	fn handle_key(&mut self, _event: &Event) {}
+
+	/// Call `visitor` with every child of this widget and its layout, in order.
+	fn visit_children<V: Visitor>(&mut self, _layout: &Layout, _visitor: &mut V) {}
 
 	fn cached(self) -> Cached<Self>
 	where
@@ -39,37 +93,188 @@ pub trait Widget {
 	where
 		Self: Sized,
 	{
-		Bordered::new(self, size)
+		Bordered::new(self, size, Color::rgb(10, 10, 10))
+	}
+
+	fn text_color(self, color: Color) -> TextStyle<Self>
+	where
+		Self: Sized,
+	{
+		TextStyle {
+			color: Some(color),
+			..TextStyle::new(self)
+		}
+	}
+
+	fn font_size(self, size: f32) -> TextStyle<Self>
+	where
+		Self: Sized,
+	{
+		TextStyle {
+			size: Some(size),
+			..TextStyle::new(self)
+		}
+	}
+
+	fn wrapped(self, wrap: Wrap) -> TextStyle<Self>
+	where
+		Self: Sized,
+	{
+		TextStyle {
+			wrap: Some(wrap),
+			..TextStyle::new(self)
+		}
+	}
+
+	fn background(self, fill: impl Into<Fill>) -> Background<Self>
+	where
+		Self: Sized,
+	{
+		Background::new(self, fill.into())
+	}
+
+	fn shadow(self, shadow: BoxShadow) -> Shadow<Self>
+	where
+		Self: Sized,
+	{
+		Shadow::new(self, shadow)
+	}
+
+	/// Add empty space around this widget, the sides are given in clockwise order starting at the
+	/// top.
+	fn padded(self, top: u32, right: u32, bottom: u32, left: u32) -> Padded<Self>
+	where
+		Self: Sized,
+	{
+		Padded::new(self, top, right, bottom, left)
+	}
+
+	/// Let this widget grow horizontally into the space its parent has left, with the given
+	/// weight.
+	fn flex_width(self, weight: f32) -> Flexible<Self>
+	where
+		Self: Sized,
+	{
+		Flexible::new(self, Some(weight), None)
+	}
+
+	/// Let this widget grow vertically into the space its parent has left, with the given
+	/// weight.
+	fn flex_height(self, weight: f32) -> Flexible<Self>
+	where
+		Self: Sized,
+	{
+		Flexible::new(self, None, Some(weight))
+	}
+
+	/// Turn this widget into a button that calls `on_click` when it is clicked.
+	fn on_click<F>(self, on_click: F) -> Button<Self, F>
+	where
+		Self: Sized,
+		F: FnMut(),
+	{
+		Button::new(self, on_click)
 	}
+
+	/// Let this widget be larger than the space it is given along `axis`, and scroll through it
+	/// with the mouse wheel.
+	fn scrollable(self, axis: ScrollAxis) -> Scroll<Self>
+	where
+		Self: Sized,
+	{
+		Scroll::new(self, axis)
+	}
+}
+
+/// Something that is called with the children of a widget, see [`Widget::visit_children`].
+pub trait Visitor {
+	fn visit<T: Widget>(&mut self, child: &mut T, layout: &Layout);
+
+	/// Visit a child that is identified by `key` instead of its position among its siblings,
+	/// like the values of a `VirtualList` which are identified by their index.
+	fn visit_keyed<T: Widget>(&mut self, _key: usize, child: &mut T, layout: &Layout) {
+		self.visit(child, layout);
+	}
+}
+
+struct Prepare(bool);
+
+impl Visitor for Prepare {
+	fn visit<T: Widget>(&mut self, child: &mut T, layout: &Layout) {
+		self.0 |= child.prepare(layout);
+	}
+}
+
+struct Changed(bool);
+
+impl Visitor for Changed {
+	fn visit<T: Widget>(&mut self, child: &mut T, layout: &Layout) {
+		self.0 = self.0 || child.changed(layout);
+	}
+}
+
+/// Lay out `widget` within `view` and prepare it, laying it out again for as long as preparing
+/// it changes it.
+pub fn prepared_layout<T: Widget>(
+	widget: &mut T,
+	context: &Context<LayoutContext>,
+	view: View,
+) -> Layout {
+	let mut layout = widget.layout(context, view.clone());
+	while widget.prepare(&layout) {
+		layout = widget.layout(context, view.clone());
+	}
+	layout
 }
 
 pub struct WidgetContext<'a> {
-	font: &'a Font,
-	device: &'a wgpu::Device,
+	layout: LayoutContext<'a>,
 	queue: &'a wgpu::Queue,
-	config: &'a wgpu::SurfaceConfiguration,
-	sampler: &'a wgpu::Sampler,
-	bind_group_layout: &'a wgpu::BindGroupLayout,
+	atlas: &'a mut GlyphAtlas,
+	text_color: Color,
 }
 
 impl<'a> WidgetContext<'a> {
 	pub fn new(
-		font: &'a Font,
-		device: &'a wgpu::Device,
+		layout: LayoutContext<'a>,
 		queue: &'a wgpu::Queue,
-		config: &'a wgpu::SurfaceConfiguration,
-		sampler: &'a wgpu::Sampler,
-		bind_group_layout: &'a wgpu::BindGroupLayout,
+		atlas: &'a mut GlyphAtlas,
 	) -> Self {
 		Self {
-			font,
-			device,
+			layout,
 			queue,
-			config,
-			sampler,
-			bind_group_layout,
+			atlas,
+			text_color: Color::BLACK,
 		}
 	}
+
+	/// The context the widgets were laid out with, with the font size of the widget that is being
+	/// rendered.
+	pub fn layout(&self) -> &LayoutContext<'a> {
+		&self.layout
+	}
+
+	fn glyph(&mut self, glyph: &Glyph) -> Option<(AtlasGlyph, PhysicalPosition<f32>)> {
+		let font = self.layout.font();
+		let bounds = font.bounds(glyph)?;
+		let atlas_glyph = self
+			.atlas
+			.glyph(font, glyph, self.queue)?;
+		Some((
+			atlas_glyph,
+			PhysicalPosition::new(bounds.min.x, bounds.min.y),
+		))
+	}
+
+	/// How many glyphs the atlas has evicted, see [`GlyphAtlas::evictions`].
+	pub fn evictions(&self) -> u64 {
+		self.atlas.evictions()
+	}
+
+	/// The color text is currently drawn in, which is black unless a `TextStyle` changes it.
+	pub fn text_color(&self) -> Color {
+		self.text_color
+	}
 }
 
 macro_rules! wrapper {
@@ -92,7 +297,7 @@ macro_rules! wrapper {
 			pub fn new($value: $ty, $($field:$field_ty),*) -> Self {
 				Self {
 					$value,
-					$($field),*
+					$($field,)*
 					$($($default_field: <$default_ty>::default()),*)?
 				}
 			}
@@ -122,466 +327,2791 @@ macro_rules! wrapper {
 
 wrapper! {
 	struct Row<T> {
-		values: Vec<T>
+		values: Vec<T>,
+
+		#default
+		justify: Justify,
+		align: Align,
+		gap: u32
 	}
 }
 
 wrapper! {
 	struct WrappingRow<T> {
-		values: Vec<T>
+		values: Vec<T>,
+
+		#default
+		justify: Justify,
+		align: Align,
+		gap: u32
+	}
+}
+
+impl<T> WrappingRow<T>
+where
+	T: Widget,
+{
+	fn rows(&self, context: &Context<LayoutContext>, view: &View) -> Vec<&[T]> {
+		let mut rows = vec![];
+		let mut start = 0;
+		let mut width = 0;
+
+		for (index, value) in self.values.iter().enumerate() {
+			let value_width = view
+				.physical_width_hint(value.width_hint(context, view))
+				.unwrap_or(0);
+
+			if index > start && width + self.gap + value_width > view.width() {
+				rows.push(&self.values[start..index]);
+				start = index;
+				width = 0;
+			}
+
+			if index > start {
+				width += self.gap;
+			}
+			width += value_width;
+		}
+
+		if start < self.values.len() {
+			rows.push(&self.values[start..]);
+		}
+
+		rows
 	}
 }
 
 wrapper! {
 	struct Column<T> {
-		values: Vec<T>
+		values: Vec<T>,
+
+		#default
+		justify: Justify,
+		align: Align,
+		gap: u32
+	}
+}
+
+macro_rules! arrangement {
+	($($name:ident),*) => {
+		$(
+			impl<T> $name<T> {
+				pub fn justified(mut self, justify: Justify) -> Self {
+					self.justify = justify;
+					self
+				}
+
+				pub fn aligned(mut self, align: Align) -> Self {
+					self.align = align;
+					self
+				}
+
+				pub fn spaced(mut self, gap: u32) -> Self {
+					self.gap = gap;
+					self
+				}
+			}
+		)*
+	};
+}
+
+arrangement!(Row, WrappingRow, Column);
+
+/// A value in a grid, placed in the cell at `row` and `column`.
+pub struct Cell<T> {
+	value: T,
+	row: usize,
+	column: usize,
+	row_span: usize,
+	column_span: usize,
+}
+
+impl<T> Cell<T> {
+	pub fn new(value: T, row: usize, column: usize) -> Self {
+		Self {
+			value,
+			row,
+			column,
+			row_span: 1,
+			column_span: 1,
+		}
+	}
+
+	/// Let this value cover `rows` rows and `columns` columns, starting from its own cell.
+	pub fn spanning(mut self, rows: usize, columns: usize) -> Self {
+		self.row_span = rows.max(1);
+		self.column_span = columns.max(1);
+		self
+	}
+}
+
+impl<T> std::ops::Deref for Cell<T> {
+	type Target = T;
+
+	fn deref(&self) -> &Self::Target {
+		&self.value
+	}
+}
+
+impl<T> std::ops::DerefMut for Cell<T> {
+	fn deref_mut(&mut self) -> &mut Self::Target {
+		&mut self.value
+	}
+}
+
+wrapper! {
+	struct Grid<T> {
+		cells: Vec<Cell<T>>,
+		columns: Vec<Track>,
+		rows: Vec<Track>,
+
+		#default
+		gap: u32
+	}
+}
+
+impl<T> Grid<T>
+where
+	T: Widget,
+{
+	pub fn spaced(mut self, gap: u32) -> Self {
+		self.gap = gap;
+		self
+	}
+
+	fn track_hints(
+		&self,
+		tracks: &[Track],
+		position: impl Fn(&Cell<T>) -> (usize, usize),
+		hint: impl Fn(&T) -> SizeHint,
+	) -> Vec<SizeHint> {
+		tracks
+			.iter()
+			.enumerate()
+			.map(|(index, track)| match *track {
+				Track::Physical(value) => SizeHint::Physical(value),
+				Track::Virtual(value) => SizeHint::Virtual(value),
+				Track::Flex(value) => SizeHint::Flex(value),
+				Track::Auto => SizeHint::Max(
+					self.cells
+						.iter()
+						.filter(|cell| position(cell) == (index, 1))
+						.map(|cell| hint(&cell.value))
+						.chain([SizeHint::Physical(0)])
+						.collect(),
+				),
+			})
+			.collect()
+	}
+
+	fn column_hints(&self, context: &Context<LayoutContext>, view: &View) -> Vec<SizeHint> {
+		self.track_hints(
+			&self.columns,
+			|cell| (cell.column, cell.column_span),
+			|value| value.width_hint(context, view),
+		)
+	}
+
+	fn row_hints(&self, context: &Context<LayoutContext>, view: &View) -> Vec<SizeHint> {
+		self.track_hints(
+			&self.rows,
+			|cell| (cell.row, cell.row_span),
+			|value| value.height_hint(context, view),
+		)
+	}
+}
+
+/// A value in a stack, attached to one of its anchors.
+pub struct Layer<T> {
+	value: T,
+	anchor: Anchor,
+	offset: (i32, i32),
+	z_index: i32,
+}
+
+impl<T> Layer<T> {
+	pub fn new(value: T, anchor: Anchor) -> Self {
+		Self {
+			value,
+			anchor,
+			offset: (0, 0),
+			z_index: 0,
+		}
+	}
+
+	/// Move this layer `x` pixels to the right and `y` pixels down from its anchor, without
+	/// leaving the stack.
+	pub fn shifted(mut self, x: i32, y: i32) -> Self {
+		self.offset = (x, y);
+		self
+	}
+
+	/// Draw this layer above every layer with a lower `z_index`. Layers with the same `z_index`
+	/// are drawn in the order they were added.
+	pub fn layered(mut self, z_index: i32) -> Self {
+		self.z_index = z_index;
+		self
+	}
+}
+
+impl<T> std::ops::Deref for Layer<T> {
+	type Target = T;
+
+	fn deref(&self) -> &Self::Target {
+		&self.value
+	}
+}
+
+impl<T> std::ops::DerefMut for Layer<T> {
+	fn deref_mut(&mut self) -> &mut Self::Target {
+		&mut self.value
+	}
+}
+
+wrapper! {
+	struct Stack<T> {
+		layers: Vec<Layer<T>>
 	}
 }
 
 wrapper! {
 	struct Bordered<T> {
 		value: T,
-		size: u32
+		size: u32,
+		color: Color,
+		#default
+		radii: Radii
 	}
 }
 
+impl<T> Bordered<T> {
+	pub fn colored(mut self, color: Color) -> Self {
+		self.color = color;
+		self
+	}
+
+	/// Round the outside corners of the border, the inside corners are rounded by as much less
+	/// as the border is wide.
+	pub fn rounded(mut self, radii: impl Into<Radii>) -> Self {
+		self.radii = radii.into();
+		self
+	}
+}
+
+// How the text in a widget is drawn, whatever is not set is taken from the widgets around it.
 wrapper! {
-	struct Cached<T: Widget> {
+	struct TextStyle<T> {
 		value: T,
+		#default
+		color: Option<Color>,
+		size: Option<f32>,
+		wrap: Option<Wrap>,
+		max_lines: Option<usize>
+	}
+}
+
+impl<T> TextStyle<T> {
+	/// Show at most `lines` lines of text, and end the last one with an ellipsis when the text
+	/// doesn't fit.
+	pub fn max_lines(mut self, lines: usize) -> Self {
+		self.max_lines = Some(lines);
+		self
+	}
+}
 
+wrapper! {
+	struct Background<T> {
+		value: T,
+		fill: Fill,
 		#default
-		cached: Option<std::rc::Rc<T::Renderable>>
+		radii: Radii
 	}
+}
 
-	#on_mut
-	fn deref_mut(&mut self) -> &mut Self::Target {
-		self.cached = None;
-		&mut self.value
+impl<T> Background<T> {
+	/// Round the corners of the background, the value in front of it is not clipped to them.
+	pub fn rounded(mut self, radii: impl Into<Radii>) -> Self {
+		self.radii = radii.into();
+		self
 	}
 }
 
-mod impls {
-	use paste::paste;
+wrapper! {
+	struct Shadow<T> {
+		value: T,
+		shadow: BoxShadow
+	}
+}
 
-	use super::*;
-	use crate::{context::Context, texture::Texture};
+wrapper! {
+	struct Padded<T> {
+		value: T,
+		top: u32,
+		right: u32,
+		bottom: u32,
+		left: u32
+	}
+}
 
-	#[cfg(feature = "text")]
-	impl Widget for char {
-		type Renderable = Option<RenderedMesh>;
+wrapper! {
+	struct Flexible<T> {
+		value: T,
+		width: Option<f32>,
+		height: Option<f32>
+	}
+}
 
-		fn get_renderable(
-			&mut self,
-			context: &mut Context<WidgetContext>,
-			view: View,
-		) -> Self::Renderable {
-			let bind_group = context.font.rasterize(
-				context.font.glyph(*self),
-				context.device,
-				context.config.format,
-				context.queue,
-				context.sampler,
-				context.bind_group_layout,
-			)?;
+/// The directions a `Scroll` can be scrolled in.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ScrollAxis {
+	Horizontal,
+	#[default]
+	Vertical,
+	Both,
+}
 
-			let width = self.width_hint(context, &view);
-			let height = self.height_hint(context, &view);
-			let view = view.from_size_hints(width, height);
-			let vertices = view.corners();
+impl ScrollAxis {
+	fn horizontal(self) -> bool {
+		matches!(self, ScrollAxis::Horizontal | ScrollAxis::Both)
+	}
+
+	fn vertical(self) -> bool {
+		matches!(self, ScrollAxis::Vertical | ScrollAxis::Both)
+	}
+}
 
-			let indices = [0, 1, 2, 2, 3, 0];
+const SCROLL_LINE: f32 = 40.0;
 
-			Some(RenderedMesh::new(
-				context.device,
-				&vertices,
-				&indices,
-				bind_group,
-			))
-		}
+/// Shows part of a value that is larger than its own view, and only draws within that view.
+pub struct Scroll<T> {
+	value: T,
+	axis: ScrollAxis,
+	offset: PhysicalPosition<f32>,
+}
 
-		fn width_hint(&self, context: &Context<WidgetContext>, _view: &View) -> SizeHint {
-			SizeHint::Physical(
-				context
-					.font
-					.glyph(*self)
-					.size()
-					.width() as u32,
-			)
+impl<T> Scroll<T> {
+	pub fn new(value: T, axis: ScrollAxis) -> Self {
+		Self {
+			value,
+			axis,
+			offset: PhysicalPosition::new(0.0, 0.0),
 		}
+	}
 
-		fn height_hint(&self, context: &Context<WidgetContext>, _view: &View) -> SizeHint {
-			SizeHint::Physical(
-				context
-					.font
-					.glyph(*self)
-					.size()
-					.height() as u32,
-			)
-		}
+	/// How far the value has been scrolled, in pixels from its top-left.
+	pub fn offset(&self) -> PhysicalPosition<f32> {
+		self.offset
 	}
 
-	#[cfg(feature = "text")]
-	impl Widget for String {
-		type Renderable = <WrappingRow<char> as Widget>::Renderable;
+	/// Scroll to `offset`, it is limited to the size of the value the next time this is laid out.
+	pub fn scroll_to(&mut self, offset: PhysicalPosition<f32>) {
+		self.offset = PhysicalPosition::new(
+			if self.axis.horizontal() {
+				offset.x.max(0.0)
+			} else {
+				0.0
+			},
+			if self.axis.vertical() {
+				offset.y.max(0.0)
+			} else {
+				0.0
+			},
+		);
+	}
 
-		fn get_renderable(
-			&mut self,
-			context: &mut Context<WidgetContext>,
-			view: View,
-		) -> Self::Renderable {
-			WrappingRow::new(self.chars().collect()).get_renderable(context, view)
+	/// Scroll by `x` and `y` pixels, it is limited to the size of the value the next time this is
+	/// laid out.
+	pub fn scroll_by(&mut self, x: f32, y: f32) {
+		self.scroll_to(PhysicalPosition::new(self.offset.x + x, self.offset.y + y));
+	}
+
+	fn max_offset(layout: &Layout) -> PhysicalPosition<f32> {
+		let view = layout.view();
+		let content = layout.children()[0].view();
+		PhysicalPosition::new(
+			content
+				.width()
+				.saturating_sub(view.width()) as f32,
+			content
+				.height()
+				.saturating_sub(view.height()) as f32,
+		)
+	}
+}
+
+impl<T> std::ops::Deref for Scroll<T> {
+	type Target = T;
+
+	fn deref(&self) -> &Self::Target {
+		&self.value
+	}
+}
+
+impl<T> std::ops::DerefMut for Scroll<T> {
+	fn deref_mut(&mut self) -> &mut Self::Target {
+		&mut self.value
+	}
+}
+
+impl<T> Scroll<T>
+where
+	T: Widget,
+{
+	fn content_size(&self, context: &Context<LayoutContext>, view: &View) -> PhysicalSize<u32> {
+		let width = if self.axis.horizontal() {
+			view.physical_width_hint(
+				self.value
+					.width_hint(context, view),
+			)
+			.unwrap_or(0)
+			.max(view.width())
+		} else {
+			view.width()
+		};
+		let height = if self.axis.vertical() {
+			view.physical_height_hint(
+				self.value
+					.height_hint(context, view),
+			)
+			.unwrap_or(0)
+			.max(view.height())
+		} else {
+			view.height()
+		};
+		PhysicalSize::new(width, height)
+	}
+}
+
+/// A scrollable list of `count` values that are all `item_height` pixels high, of which only the
+/// visible ones exist.
+pub struct VirtualList<T, F> {
+	count: usize,
+	item_height: u32,
+	builder: F,
+	items: Vec<(usize, T)>,
+	spare: Vec<T>,
+	stale: bool,
+	offset: f32,
+}
+
+impl<T, F> VirtualList<T, F>
+where
+	T: Widget,
+	F: FnMut(usize, Option<T>) -> T,
+{
+	pub fn new(count: usize, item_height: u32, builder: F) -> Self {
+		Self {
+			count,
+			item_height,
+			builder,
+			items: vec![],
+			spare: vec![],
+			stale: false,
+			offset: 0.0,
+		}
+	}
+
+	pub fn count(&self) -> usize {
+		self.count
+	}
+
+	/// Change the number of values, every value is built again the next time this is prepared.
+	pub fn set_count(&mut self, count: usize) {
+		self.count = count;
+		self.refresh();
+	}
+
+	/// Build every visible value again the next time this is prepared, for when the data they
+	/// show has changed.
+	pub fn refresh(&mut self) {
+		self.stale = true;
+	}
+
+	/// How far the list has been scrolled, in pixels from its top.
+	pub fn offset(&self) -> f32 {
+		self.offset
+	}
+
+	/// Scroll to `offset`, it is limited to the height of the list the next time this is laid out.
+	pub fn scroll_to(&mut self, offset: f32) {
+		self.offset = offset.max(0.0);
+	}
+
+	/// Scroll by `y` pixels, it is limited to the height of the list the next time this is laid
+	/// out.
+	pub fn scroll_by(&mut self, y: f32) {
+		self.scroll_to(self.offset + y);
+	}
+
+	fn content_height(&self) -> u32 {
+		(self.count as u32).saturating_mul(self.item_height)
+	}
+
+	fn clamped_offset(&self, height: u32) -> u32 {
+		(self.offset as u32).min(
+			self.content_height()
+				.saturating_sub(height),
+		)
+	}
+
+	fn visible(&self, height: u32) -> std::ops::Range<usize> {
+		let offset = self.clamped_offset(height);
+		let item_height = self.item_height.max(1);
+
+		let first = (offset / item_height) as usize;
+		let last = (offset + height).div_ceil(item_height) as usize;
+		first.min(self.count)..last.min(self.count)
+	}
+
+	fn update(&mut self, range: std::ops::Range<usize>, layout: &Layout) -> bool {
+		if !self.stale
+			&& self
+				.items
+				.iter()
+				.map(|(index, _)| *index)
+				.eq(range.clone())
+		{
+			return false;
+		}
+
+		let stale = std::mem::take(&mut self.stale);
+		let mut layouts = layout.children().iter();
+		let mut visible = vec![];
+		for (index, mut item) in std::mem::take(&mut self.items) {
+			let layout = layouts.next();
+			if stale || !range.contains(&index) {
+				// Focus can't follow a value out of the list.
+				if let Some(layout) = layout {
+					focus::blur(&mut item, layout);
+				}
+				self.spare.push(item);
+			} else {
+				visible.push((index, item));
+			}
+		}
+
+		for index in range {
+			if !visible
+				.iter()
+				.any(|(x, _)| *x == index)
+			{
+				visible.push((index, (self.builder)(index, self.spare.pop())));
+			}
+		}
+		visible.sort_by_key(|(index, _)| *index);
+
+		self.items = visible;
+		true
+	}
+}
+
+/// An editable line of text, with a caret and a selection.
+#[cfg(feature = "text")]
+#[derive(Debug, Default)]
+pub struct TextInput {
+	value: String,
+	caret: usize,
+	/// The other end of the selection, the caret being the end that moves.
+	anchor: Option<usize>,
+	preedit: Option<String>,
+	focused: bool,
+	dragging: bool,
+	modifiers: Modifiers,
+	changed: bool,
+}
+
+#[cfg(feature = "text")]
+impl TextInput {
+	/// An input containing `value`, with the caret at its end.
+	pub fn new(value: impl Into<String>) -> Self {
+		let value = value.into();
+		Self {
+			caret: value.len(),
+			value,
+			..Self::default()
+		}
+	}
+
+	pub fn value(&self) -> &str {
+		&self.value
+	}
+
+	/// Replace the text, which moves the caret to its end and clears the selection.
+	pub fn set_value(&mut self, value: impl Into<String>) {
+		*self = Self {
+			focused: self.focused,
+			modifiers: self.modifiers,
+			..Self::new(value)
+		};
+	}
+
+	pub fn caret(&self) -> usize {
+		self.caret
+	}
+
+	/// The selected part of the text, if any of it is selected.
+	pub fn selection(&self) -> Option<std::ops::Range<usize>> {
+		let anchor = self.anchor?;
+		let range = anchor.min(self.caret)..anchor.max(self.caret);
+		(!range.is_empty()).then_some(range)
+	}
+
+	pub fn selected_text(&self) -> &str {
+		self.selection()
+			.map_or("", |range| &self.value[range])
+	}
+
+	/// Move the caret to `position`, selecting the text in between when `select` is set.
+	pub fn move_caret(&mut self, position: usize, select: bool) {
+		let mut position = position.min(self.value.len());
+		while !self
+			.value
+			.is_char_boundary(position)
+		{
+			position -= 1;
+		}
+
+		if select {
+			self.anchor
+				.get_or_insert(self.caret);
+		} else {
+			self.anchor = None;
+		}
+		self.caret = position;
+	}
+
+	pub fn select_all(&mut self) {
+		self.anchor = Some(0);
+		self.caret = self.value.len();
+	}
+
+	/// Insert `text` at the caret, replacing the selection.
+	pub fn insert(&mut self, text: &str) {
+		self.delete_selection();
+		self.value
+			.insert_str(self.caret, text);
+		self.caret += text.len();
+	}
+
+	fn delete_selection(&mut self) {
+		if let Some(range) = self.selection() {
+			self.value
+				.replace_range(range.clone(), "");
+			self.caret = range.start;
+		}
+		self.anchor = None;
+	}
+
+	fn previous(&self, position: usize) -> usize {
+		self.value[..position]
+			.char_indices()
+			.next_back()
+			.map_or(0, |(index, _)| index)
+	}
+
+	fn next(&self, position: usize) -> usize {
+		self.value[position..]
+			.chars()
+			.next()
+			.map_or(position, |c| position + c.len_utf8())
+	}
+
+	fn press(&mut self, key: Key) {
+		let shift = self.modifiers.shift;
+		match key {
+			Key::A if self.modifiers.ctrl => self.select_all(),
+			// Without a selection, these delete the character next to the caret by selecting it.
+			Key::Back => {
+				if self.selection().is_none() {
+					self.move_caret(self.previous(self.caret), true);
+				}
+				self.delete_selection();
+			}
+			Key::Delete => {
+				if self.selection().is_none() {
+					self.move_caret(self.next(self.caret), true);
+				}
+				self.delete_selection();
+			}
+			Key::Left => match self.selection() {
+				Some(range) if !shift => self.move_caret(range.start, false),
+				_ => self.move_caret(self.previous(self.caret), shift),
+			},
+			Key::Right => match self.selection() {
+				Some(range) if !shift => self.move_caret(range.end, false),
+				_ => self.move_caret(self.next(self.caret), shift),
+			},
+			Key::Home => self.move_caret(0, shift),
+			Key::End => self.move_caret(self.value.len(), shift),
+			_ => {}
+		}
+	}
+
+	fn displayed(&self) -> (String, usize) {
+		let mut text = self.value.clone();
+		text.insert_str(
+			self.caret,
+			self.preedit
+				.as_deref()
+				.unwrap_or(""),
+		);
+		let caret = self.value[..self.caret]
+			.chars()
+			.count() + self
+			.preedit
+			.as_deref()
+			.map_or(0, |x| x.chars().count());
+		(text, caret)
+	}
+}
+
+/// A rectangle that covers all of the space it is given with a fill, and optionally has rounded
+/// corners and a border.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Rect {
+	shape: Shape,
+}
+
+impl Rect {
+	pub fn new(fill: impl Into<Fill>) -> Self {
+		Self {
+			shape: Shape::new(fill),
+		}
+	}
+
+	pub fn rounded(mut self, radii: impl Into<Radii>) -> Self {
+		self.shape = self.shape.rounded(radii);
+		self
+	}
+
+	/// Draw a border of `size` pixels in `color` along the inside of this rectangle.
+	pub fn outlined(mut self, size: u32, color: Color) -> Self {
+		self.shape = self
+			.shape
+			.outlined(size as f32, color);
+		self
+	}
+
+	pub fn fill(&self) -> &Fill {
+		self.shape.fill()
+	}
+
+	pub fn set_fill(&mut self, fill: impl Into<Fill>) {
+		let (width, color) = self.shape.border();
+		self.shape = Shape::new(fill)
+			.rounded(self.shape.radii())
+			.outlined(width, color);
+	}
+
+	pub fn shape(&self) -> &Shape {
+		&self.shape
+	}
+}
+
+/// A value that can be clicked, which calls `on_click` when the mouse button is released over it
+/// or when Enter or Space is pressed while it has focus.
+pub struct Button<T, F> {
+	value: T,
+	on_click: F,
+	hovered: bool,
+	pressed: bool,
+	drawn: Option<(bool, bool)>,
+}
+
+impl<T, F> Button<T, F>
+where
+	F: FnMut(),
+{
+	pub fn new(value: T, on_click: F) -> Self {
+		Self {
+			value,
+			on_click,
+			hovered: false,
+			pressed: false,
+			drawn: None,
+		}
+	}
+
+	pub fn hovered(&self) -> bool {
+		self.hovered
+	}
+
+	/// Whether a mouse button was pressed on the button and not yet released.
+	pub fn pressed(&self) -> bool {
+		self.pressed
+	}
+
+	/// Call `on_click`, as if the button was clicked.
+	pub fn click(&mut self) {
+		(self.on_click)();
+	}
+
+	fn fill(&self) -> Fill {
+		Fill::Solid(if self.pressed {
+			Color::rgb(150, 150, 150)
+		} else if self.hovered {
+			Color::rgb(230, 230, 230)
+		} else {
+			Color::rgb(200, 200, 200)
+		})
+	}
+}
+
+impl<T, F> std::ops::Deref for Button<T, F> {
+	type Target = T;
+
+	fn deref(&self) -> &Self::Target {
+		&self.value
+	}
+}
+
+impl<T, F> std::ops::DerefMut for Button<T, F> {
+	fn deref_mut(&mut self) -> &mut Self::Target {
+		&mut self.value
+	}
+}
+
+wrapper! {
+	struct Cached<T: Widget> {
+		value: T,
+
+		#default
+		cached: Option<(std::rc::Rc<T::Renderable>, u64, Layout)>
+	}
+
+	#on_mut
+	fn deref_mut(&mut self) -> &mut Self::Target {
+		self.cached = None;
+		&mut self.value
+	}
+}
+
+impl<T: Widget> Cached<T> {
+	/// Render the value again the next time this is rendered, for when it changed in a way it
+	/// doesn't report through [`Widget::changed`].
+	pub fn invalidate(&mut self) {
+		self.cached = None;
+	}
+}
+
+mod impls {
+	use paste::paste;
+
+	use super::*;
+	use crate::context::Context;
+
+	#[cfg(feature = "text")]
+	impl Widget for char {
+		type Renderable = Option<RenderedMesh>;
+
+		fn layout(&self, context: &Context<LayoutContext>, view: View) -> Layout {
+			let width = self.width_hint(context, &view);
+			let height = self.height_hint(context, &view);
+			Layout::leaf(view.from_size_hints(width, height))
+		}
+
+		fn get_renderable(
+			&mut self,
+			context: &mut Context<WidgetContext>,
+			layout: &Layout,
+		) -> Self::Renderable {
+			let glyph = context.layout.glyph(*self);
+			let baseline = glyph.baseline() as f32;
+			let vertices = glyph_vertices(context, &glyph, layout.view(), (0.0, baseline))?;
+
+			Some(RenderedMesh::new(
+				vertices.to_vec(),
+				vec![0, 1, 2, 2, 3, 0],
+				context.atlas.bind_group().clone(),
+			))
+		}
+
+		fn width_hint(&self, context: &Context<LayoutContext>, _view: &View) -> SizeHint {
+			SizeHint::Physical(context.glyph(*self).size().width)
+		}
+
+		fn height_hint(&self, context: &Context<LayoutContext>, _view: &View) -> SizeHint {
+			SizeHint::Physical(context.glyph(*self).size().height)
+		}
+
+		fn baseline(&self, context: &Context<LayoutContext>, _view: &View) -> Option<u32> {
+			Some(context.glyph(*self).baseline())
+		}
+	}
+
+	#[cfg(feature = "text")]
+	fn glyph_vertices(
+		context: &mut Context<WidgetContext>,
+		glyph: &Glyph,
+		view: &View,
+		position: (f32, f32),
+	) -> Option<[Vertex; 4]> {
+		let glyph = glyph
+			.clone()
+			.with_offset(position.0);
+		let (atlas_glyph, origin) = context.glyph(&glyph)?;
+
+		// The outline may reach past the view, like the tail of a `j`.
+		let left = position.0.floor() + origin.x;
+		let top = position.1.round() + origin.y;
+		let size = atlas_glyph.size();
+
+		let color = context.text_color.to_linear();
+		Some(
+			[[0.0, 0.0], [0.0, 1.0], [1.0, 1.0], [1.0, 0.0]].map(|[x, y]| {
+				let position = view
+					.globalize_physical(left + x * size.width as f32, top + y * size.height as f32);
+				Vertex::new(position, atlas_glyph.uv([x, y])).with_color(color)
+			}),
+		)
+	}
+
+	#[cfg(feature = "text")]
+	impl Widget for String {
+		type Renderable = Option<RenderedMesh>;
+
+		fn layout(&self, context: &Context<LayoutContext>, view: View) -> Layout {
+			let paragraph = context.paragraph(self, Some(view.width()));
+			let line_height = paragraph.line_height();
+
+			let lines = paragraph.lines();
+			let (shown, end) = lines
+				.last()
+				.map_or((0, (0, 0.0)), |x| {
+					let right = x
+						.characters
+						.last()
+						.map_or(0.0, |x| x.1);
+					(x.range.end, (lines.len() - 1, right))
+				});
+			let left_out = self[shown..]
+				.chars()
+				.map(|_| (end.0, end.1, end.1));
+
+			let children = lines
+				.iter()
+				.enumerate()
+				.flat_map(|(index, line)| {
+					line.characters
+						.iter()
+						.map(move |(left, right)| (index, *left, *right))
+				})
+				.chain(left_out)
+				.map(|(index, left, right)| {
+					let (left, right) = (left.round() as u32, right.round() as u32);
+					Layout::leaf(view.sub_view(
+						PhysicalPosition::new(left, index as u32 * line_height),
+						PhysicalSize::new(right.saturating_sub(left), line_height),
+					))
+				})
+				.collect();
+
+			Layout::new(view, children)
+		}
+
+		fn get_renderable(
+			&mut self,
+			context: &mut Context<WidgetContext>,
+			layout: &Layout,
+		) -> Self::Renderable {
+			let view = layout.view();
+			let paragraph = context
+				.layout
+				.paragraph(self, Some(view.width()));
+
+			let mut vertices = vec![];
+			let mut indices = vec![];
+			for (index, line) in paragraph
+				.lines()
+				.iter()
+				.enumerate()
+			{
+				let baseline =
+					(index as u32 * paragraph.line_height() + paragraph.baseline()) as f32;
+				let mut x = 0.0;
+				for glyph in line
+					.runs
+					.iter()
+					.flat_map(|x| &x.glyphs)
+				{
+					let position = (x + glyph.offset.0, baseline + glyph.offset.1);
+					if let Some(corners) = glyph_vertices(context, &glyph.glyph, view, position) {
+						let offset = vertices.len() as u32;
+						indices.extend([0, 1, 2, 2, 3, 0].map(|x| x + offset));
+						vertices.extend(corners);
+					}
+					x += glyph.advance;
+				}
+			}
+
+			if vertices.is_empty() {
+				return None;
+			}
+			Some(RenderedMesh::new(
+				vertices,
+				indices,
+				context.atlas.bind_group().clone(),
+			))
+		}
+
+		fn width_hint(&self, context: &Context<LayoutContext>, view: &View) -> SizeHint {
+			let width = context
+				.paragraph(self, None)
+				.width();
+			SizeHint::Min(vec![
+				SizeHint::Physical(width),
+				SizeHint::Physical(view.width()),
+			])
+		}
+
+		fn height_hint(&self, context: &Context<LayoutContext>, view: &View) -> SizeHint {
+			SizeHint::Physical(
+				context
+					.paragraph(self, Some(view.width()))
+					.height(),
+			)
+		}
+
+		fn baseline(&self, context: &Context<LayoutContext>, _view: &View) -> Option<u32> {
+			let paragraph = context.paragraph(self, None);
+			(!paragraph.lines().is_empty()).then(|| paragraph.baseline())
+		}
+	}
+
+	fn filled(views: &[View], fill: &Fill) -> Option<RenderedMesh> {
+		if views.is_empty() {
+			return None;
+		}
+
+		let mut vertices = vec![];
+		let mut indices = vec![];
+		for view in views {
+			let (view_vertices, view_indices) = fill.mesh(view);
+			let offset = vertices.len() as u32;
+			indices.extend(
+				view_indices
+					.into_iter()
+					.map(|x| x + offset),
+			);
+			vertices.extend(view_vertices);
+		}
+
+		Some(RenderedMesh::untextured(vertices, indices))
+	}
+
+	fn shaped(view: &View, shape: &Shape) -> RenderedShape {
+		let (vertices, indices) = shape.mesh(view);
+		RenderedShape::new(vertices, indices)
+	}
+
+	#[cfg(feature = "text")]
+	const CARET_WIDTH: u32 = 4;
+
+	#[cfg(feature = "text")]
+	impl TextInput {
+		fn caret_at(layout: &Layout, index: usize) -> Option<(i32, i32, u32)> {
+			let origin = layout.view().position();
+			let characters = layout.children()[0].children();
+
+			let (view, x) = match characters.get(index) {
+				Some(character) => (character.view(), character.view().position().x),
+				None => {
+					let view = characters.last()?.view();
+					(view, view.position().x + view.width() as i32)
+				}
+			};
+			Some((x - origin.x, view.position().y - origin.y, view.height()))
+		}
+
+		fn offset_at(&self, layout: &Layout, position: PhysicalPosition<f64>) -> usize {
+			let count = self.value.chars().count();
+			let carets = (0..=count)
+				.filter_map(|index| Some((index, Self::caret_at(layout, index)?)))
+				.collect::<Vec<_>>();
+
+			let on_line = |(_, (_, y, height)): &&(usize, (i32, i32, u32))| {
+				position.y >= *y as f64 && position.y < (*y + *height as i32) as f64
+			};
+			let line = if carets.iter().any(|x| on_line(&x)) {
+				carets
+					.iter()
+					.filter(on_line)
+					.collect::<Vec<_>>()
+			} else {
+				carets.iter().collect()
+			};
+
+			let index = line
+				.into_iter()
+				.min_by(|(_, (a, _, _)), (_, (b, _, _))| {
+					let a = (*a as f64 - position.x).abs();
+					let b = (*b as f64 - position.x).abs();
+					a.total_cmp(&b)
+				})
+				.map_or(0, |(index, _)| *index);
+
+			self.value
+				.char_indices()
+				.nth(index)
+				.map_or(self.value.len(), |(offset, _)| offset)
+		}
+	}
+
+	#[cfg(feature = "text")]
+	impl Widget for TextInput {
+		type Renderable = (
+			Option<RenderedMesh>,
+			<String as Widget>::Renderable,
+			Option<RenderedMesh>,
+		);
+
+		fn layout(&self, context: &Context<LayoutContext>, view: View) -> Layout {
+			let (text, _) = self.displayed();
+			let child = text.layout(context, view.clone());
+			Layout::new(view, vec![child])
+		}
+
+		fn get_renderable(
+			&mut self,
+			context: &mut Context<WidgetContext>,
+			layout: &Layout,
+		) -> Self::Renderable {
+			self.changed = false;
+			let view = layout.view();
+			let characters = layout.children()[0].children();
+
+			let selected = match (&self.preedit, self.selection()) {
+				(None, Some(range)) => {
+					let start = self.value[..range.start]
+						.chars()
+						.count();
+					let count = self.value[range].chars().count();
+					characters
+						.iter()
+						.skip(start)
+						.take(count)
+						.map(|x| x.view().clone())
+						.collect()
+				}
+				_ => vec![],
+			};
+			let selection = filled(&selected, &Fill::Solid(Color::rgb(120, 170, 255)));
+
+			let (mut text, caret) = self.displayed();
+			let text = text.get_renderable(context, &layout.children()[0]);
+
+			let caret = if self.focused {
+				let line_height = context
+					.layout
+					.glyph(' ')
+					.size()
+					.height;
+				let (x, y, height) = Self::caret_at(layout, caret).unwrap_or((0, 0, line_height));
+				let caret = view.sub_view(
+					PhysicalPosition::new(x.max(0) as u32, y.max(0) as u32),
+					PhysicalSize::new(CARET_WIDTH, height),
+				);
+				filled(&[caret], &Fill::Solid(Color::rgb(10, 10, 10)))
+			} else {
+				None
+			};
+
+			(selection, text, caret)
+		}
+
+		fn changed(&mut self, _layout: &Layout) -> bool {
+			self.changed
+		}
+
+		fn width_hint(&self, context: &Context<LayoutContext>, view: &View) -> SizeHint {
+			let (text, _) = self.displayed();
+			SizeHint::Sum(vec![
+				text.width_hint(context, view),
+				SizeHint::Physical(CARET_WIDTH),
+			])
+		}
+
+		fn height_hint(&self, context: &Context<LayoutContext>, view: &View) -> SizeHint {
+			let (text, _) = self.displayed();
+			SizeHint::Max(vec![
+				text.height_hint(context, view),
+				' '.height_hint(context, view),
+			])
+		}
+
+		fn baseline(&self, context: &Context<LayoutContext>, view: &View) -> Option<u32> {
+			let (text, _) = self.displayed();
+			text.baseline(context, view)
+				.or(' '.baseline(context, view))
+		}
+
+		fn handle_pointer(&mut self, event: &PointerEvent, layout: &Layout) -> Propagation {
+			match event.kind() {
+				PointerKind::Button {
+					button: MouseButton::Left,
+					state,
+				} => {
+					self.dragging = state == ElementState::Pressed;
+					self.changed = true;
+					if self.dragging && self.preedit.is_none() {
+						let offset = self.offset_at(layout, event.position());
+						self.move_caret(offset, self.modifiers.shift);
+					}
+					Propagation::Stop
+				}
+				PointerKind::Moved if self.dragging && self.preedit.is_none() => {
+					let offset = self.offset_at(layout, event.position());
+					self.move_caret(offset, true);
+					self.changed = true;
+					Propagation::Stop
+				}
+				PointerKind::Exited => {
+					self.dragging = false;
+					Propagation::Continue
+				}
+				_ => Propagation::Continue,
+			}
+		}
+
+		fn focusable(&self) -> bool {
+			true
+		}
+
+		fn focus_changed(&mut self, focused: bool) {
+			self.focused = focused;
+			self.changed = true;
+		}
+
+		fn handle_key(&mut self, event: &Event) {
+			self.changed = true;
+			match event {
+				Event::ModifiersChanged(modifiers) => self.modifiers = *modifiers,
+				Event::Text(character) if !character.is_control() => {
+					self.insert(character.encode_utf8(&mut [0; 4]));
+				}
+				Event::Keyboard {
+					key,
+					state: ElementState::Pressed,
+				} => self.press(*key),
+				Event::Ime(Ime::Preedit(text, _)) => {
+					if text.is_empty() {
+						self.preedit = None;
+					} else {
+						self.delete_selection();
+						self.preedit = Some(text.clone());
+					}
+				}
+				Event::Ime(Ime::Commit(text)) => {
+					self.preedit = None;
+					self.insert(text);
+				}
+				Event::Ime(Ime::Disabled) => self.preedit = None,
+				_ => {}
+			}
+		}
+	}
+
+	impl<T, F> Widget for Button<T, F>
+	where
+		T: Widget,
+		F: FnMut(),
+	{
+		type Renderable = (Option<RenderedMesh>, T::Renderable);
+
+		fn layout(&self, context: &Context<LayoutContext>, view: View) -> Layout {
+			let width = self.width_hint(context, &view);
+			let height = self.height_hint(context, &view);
+			let view = view.from_size_hints(width, height);
+
+			Layout::new(view.clone(), vec![self.value.layout(context, view)])
+		}
+
+		fn get_renderable(
+			&mut self,
+			context: &mut Context<WidgetContext>,
+			layout: &Layout,
+		) -> Self::Renderable {
+			let background = filled(&[layout.view().clone()], &self.fill());
+			self.drawn = Some((self.hovered, self.pressed));
+
+			(
+				background,
+				self.value
+					.get_renderable(context, &layout.children()[0]),
+			)
+		}
+
+		fn changed(&mut self, layout: &Layout) -> bool {
+			self.drawn != Some((self.hovered, self.pressed))
+				|| self
+					.value
+					.changed(&layout.children()[0])
+		}
+
+		fn width_hint(&self, context: &Context<LayoutContext>, view: &View) -> SizeHint {
+			self.value
+				.width_hint(context, view)
+		}
+
+		fn height_hint(&self, context: &Context<LayoutContext>, view: &View) -> SizeHint {
+			self.value
+				.height_hint(context, view)
+		}
+
+		fn baseline(&self, context: &Context<LayoutContext>, view: &View) -> Option<u32> {
+			self.value.baseline(context, view)
+		}
+
+		fn resize(&mut self, new_size: PhysicalSize<u32>) {
+			self.value.resize(new_size);
+		}
+
+		fn handle(&mut self, event: &Event) {
+			self.value.handle(event);
+		}
+
+		fn handle_pointer(&mut self, event: &PointerEvent, layout: &Layout) -> Propagation {
+			let child = (&mut self.value, &layout.children()[0]);
+			if route(event, std::iter::once(child)) == Propagation::Stop {
+				return Propagation::Stop;
+			}
+
+			match event.kind() {
+				PointerKind::Moved => {
+					self.hovered = true;
+					Propagation::Continue
+				}
+				PointerKind::Exited => {
+					self.hovered = false;
+					self.pressed = false;
+					Propagation::Continue
+				}
+				PointerKind::Button {
+					button: MouseButton::Left,
+					state,
+				} => {
+					self.hovered = true;
+					let clicked = self.pressed && state == ElementState::Released;
+					self.pressed = state == ElementState::Pressed;
+					if clicked {
+						self.click();
+					}
+					Propagation::Stop
+				}
+				_ => Propagation::Continue,
+			}
+		}
+
+		fn focusable(&self) -> bool {
+			true
+		}
+
+		fn handle_key(&mut self, event: &Event) {
+			if let Event::Keyboard {
+				key: Key::Return | Key::Space,
+				state: ElementState::Pressed,
+			} = event
+			{
+				self.click();
+			}
+		}
+
+		fn visit_children<V: Visitor>(&mut self, layout: &Layout, visitor: &mut V) {
+			visitor.visit(&mut self.value, &layout.children()[0]);
+		}
+	}
+
+	impl<T> Widget for &mut T
+	where
+		T: Widget,
+	{
+		type Renderable = T::Renderable;
+
+		fn layout(&self, context: &Context<LayoutContext>, view: View) -> Layout {
+			(**self).layout(context, view)
+		}
+
+		fn prepare(&mut self, layout: &Layout) -> bool {
+			(**self).prepare(layout)
+		}
+
+		fn changed(&mut self, layout: &Layout) -> bool {
+			(**self).changed(layout)
+		}
+
+		fn get_renderable(
+			&mut self,
+			context: &mut Context<WidgetContext>,
+			layout: &Layout,
+		) -> Self::Renderable {
+			(**self).get_renderable(context, layout)
+		}
+
+		fn resize(&mut self, new_size: PhysicalSize<u32>) {
+			(**self).resize(new_size);
+		}
+
+		fn handle(&mut self, event: &Event) {
+			(**self).handle(event);
+		}
+
+		fn handle_pointer(&mut self, event: &PointerEvent, layout: &Layout) -> Propagation {
+			(**self).handle_pointer(event, layout)
+		}
+
+		fn focusable(&self) -> bool {
+			(**self).focusable()
+		}
+
+		fn focus_changed(&mut self, focused: bool) {
+			(**self).focus_changed(focused);
+		}
+
+		fn handle_key(&mut self, event: &Event) {
+			(**self).handle_key(event);
+		}
+
+		fn width_hint(&self, context: &Context<LayoutContext>, view: &View) -> SizeHint {
+			(**self).width_hint(context, view)
+		}
+
+		fn height_hint(&self, context: &Context<LayoutContext>, view: &View) -> SizeHint {
+			(**self).height_hint(context, view)
+		}
+
+		fn baseline(&self, context: &Context<LayoutContext>, view: &View) -> Option<u32> {
+			(**self).baseline(context, view)
+		}
+
+		fn visit_children<V: Visitor>(&mut self, layout: &Layout, visitor: &mut V) {
+			(**self).visit_children(layout, visitor);
+		}
+	}
+
+	fn layout_row<T: Widget>(
+		values: &[T],
+		context: &Context<LayoutContext>,
+		view: &View,
+		justify: Justify,
+		align: Align,
+		gap: u32,
+	) -> Vec<Layout> {
+		let hints = values
+			.iter()
+			.map(|x| x.width_hint(context, view))
+			.collect();
+		let views = view
+			.clone()
+			.split_row(hints, justify, gap);
+
+		let placements = values
+			.iter()
+			.zip(&views)
+			.map(|(w, v)| {
+				// Only stretching lets a value grow into the height of the row, otherwise it gets
+				// the height it needs without growing. Values that don't need any height take all
+				// of it.
+				let height = v
+					.physical_height_hint(w.height_hint(context, v))
+					.unwrap_or(v.height());
+				(height, w.baseline(context, v))
+			})
+			.collect::<Vec<_>>();
+
+		let line_baseline = placements
+			.iter()
+			.map(|(height, baseline)| baseline.unwrap_or(*height))
+			.max()
+			.unwrap_or(0);
+
+		values
+			.iter()
+			.zip(views)
+			.zip(placements)
+			.map(|((w, v), (height, baseline))| {
+				let (offset, height) = align.place(v.height(), height, baseline, line_baseline);
+				let view = v.sub_view(
+					PhysicalPosition::new(0, offset),
+					PhysicalSize::new(v.width(), height),
+				);
+				w.layout(context, view)
+			})
+			.collect()
+	}
+
+	fn gaps(count: usize, gap: u32) -> SizeHint {
+		SizeHint::Physical(gap * (count as u32).saturating_sub(1))
+	}
+
+	fn row_height_hint<T: Widget>(
+		values: &[T],
+		context: &Context<LayoutContext>,
+		view: &View,
+		align: Align,
+	) -> SizeHint {
+		let heights = values
+			.iter()
+			.map(|x| x.height_hint(context, view));
+
+		if align != Align::Baseline {
+			return SizeHint::Max(heights.collect());
+		}
+
+		// The values hang from the same baseline, so the row has to fit both the highest value
+		// above it and the lowest value below it.
+		let (above, below) = values
+			.iter()
+			.zip(heights)
+			.map(|(w, hint)| {
+				let height = view
+					.physical_height_hint(hint)
+					.unwrap_or(0);
+				let baseline = w
+					.baseline(context, view)
+					.unwrap_or(height)
+					.min(height);
+				(baseline, height - baseline)
+			})
+			.fold((0, 0), |(above, below), (a, b)| {
+				(above.max(a), below.max(b))
+			});
+
+		SizeHint::Physical(above + below)
+	}
+
+	fn row_baseline<T: Widget>(
+		values: &[T],
+		context: &Context<LayoutContext>,
+		view: &View,
+		align: Align,
+	) -> Option<u32> {
+		match align {
+			Align::Baseline => values
+				.iter()
+				.map(|w| {
+					w.baseline(context, view)
+						.or_else(|| view.physical_height_hint(w.height_hint(context, view)))
+				})
+				.max()
+				.flatten(),
+			Align::Start | Align::Stretch => values
+				.first()?
+				.baseline(context, view),
+			Align::Center | Align::End => None,
+		}
+	}
+
+	impl<T> Widget for Row<T>
+	where
+		T: Widget,
+	{
+		type Renderable = Vec<T::Renderable>;
+
+		fn layout(&self, context: &Context<LayoutContext>, view: View) -> Layout {
+			let children = layout_row(
+				&self.values,
+				context,
+				&view,
+				self.justify,
+				self.align,
+				self.gap,
+			);
+
+			Layout::new(view, children)
+		}
+
+		fn get_renderable(
+			&mut self,
+			context: &mut Context<WidgetContext>,
+			layout: &Layout,
+		) -> Self::Renderable {
+			self.values
+				.iter_mut()
+				.zip(layout.children())
+				.map(|(w, l)| w.get_renderable(context, l))
+				.collect()
+		}
+
+		fn width_hint(&self, context: &Context<LayoutContext>, view: &View) -> SizeHint {
+			SizeHint::Sum(
+				self.values
+					.iter()
+					.map(|x| x.width_hint(context, view))
+					.chain([gaps(self.values.len(), self.gap)])
+					.collect(),
+			)
+		}
+
+		fn height_hint(&self, context: &Context<LayoutContext>, view: &View) -> SizeHint {
+			row_height_hint(&self.values, context, view, self.align)
+		}
+
+		fn baseline(&self, context: &Context<LayoutContext>, view: &View) -> Option<u32> {
+			row_baseline(&self.values, context, view, self.align)
+		}
+
+		fn handle(&mut self, event: &Event) {
+			for value in &mut self.values {
+				value.handle(event);
+			}
+		}
+
+		fn handle_pointer(&mut self, event: &PointerEvent, layout: &Layout) -> Propagation {
+			route(
+				event,
+				self.values
+					.iter_mut()
+					.zip(layout.children()),
+			)
+		}
+
+		fn visit_children<V: Visitor>(&mut self, layout: &Layout, visitor: &mut V) {
+			for (value, layout) in self
+				.values
+				.iter_mut()
+				.zip(layout.children())
+			{
+				visitor.visit(value, layout);
+			}
+		}
+	}
+
+	impl<T> Widget for WrappingRow<T>
+	where
+		T: Widget,
+	{
+		type Renderable = Vec<T::Renderable>;
+
+		fn layout(&self, context: &Context<LayoutContext>, view: View) -> Layout {
+			let rows = self.rows(context, &view);
+
+			let heights = rows
+				.iter()
+				.map(|row| row_height_hint(row, context, &view, self.align))
+				.collect();
+			let row_views = view
+				.clone()
+				.split_column(heights, Justify::Start, self.gap);
+
+			let children = rows
+				.into_iter()
+				.zip(row_views)
+				.flat_map(|(row, row_view)| {
+					layout_row(row, context, &row_view, self.justify, self.align, self.gap)
+				})
+				.collect();
+
+			Layout::new(view, children)
+		}
+
+		fn get_renderable(
+			&mut self,
+			context: &mut Context<WidgetContext>,
+			layout: &Layout,
+		) -> Self::Renderable {
+			self.values
+				.iter_mut()
+				.zip(layout.children())
+				.map(|(w, l)| w.get_renderable(context, l))
+				.collect()
+		}
+
+		fn handle(&mut self, event: &Event) {
+			for value in &mut self.values {
+				value.handle(event);
+			}
+		}
+
+		fn handle_pointer(&mut self, event: &PointerEvent, layout: &Layout) -> Propagation {
+			route(
+				event,
+				self.values
+					.iter_mut()
+					.zip(layout.children()),
+			)
+		}
+
+		fn width_hint(&self, context: &Context<LayoutContext>, view: &View) -> SizeHint {
+			let sum = self
+				.values
+				.iter()
+				.map(|x| x.width_hint(context, view))
+				.chain([gaps(self.values.len(), self.gap)])
+				.collect();
+			SizeHint::Min(vec![SizeHint::Sum(sum), SizeHint::Physical(view.width())])
+		}
+
+		fn height_hint(&self, context: &Context<LayoutContext>, view: &View) -> SizeHint {
+			let rows = self.rows(context, view);
+			let gaps = gaps(rows.len(), self.gap);
+
+			SizeHint::Sum(
+				rows.into_iter()
+					.map(|row| row_height_hint(row, context, view, self.align))
+					.chain([gaps])
+					.collect(),
+			)
+		}
+
+		fn baseline(&self, context: &Context<LayoutContext>, view: &View) -> Option<u32> {
+			let rows = self.rows(context, view);
+			row_baseline(rows.first()?, context, view, self.align)
+		}
+
+		fn visit_children<V: Visitor>(&mut self, layout: &Layout, visitor: &mut V) {
+			for (value, layout) in self
+				.values
+				.iter_mut()
+				.zip(layout.children())
+			{
+				visitor.visit(value, layout);
+			}
+		}
+	}
+
+	impl<T> Widget for Column<T>
+	where
+		T: Widget,
+	{
+		type Renderable = Vec<T::Renderable>;
+
+		fn layout(&self, context: &Context<LayoutContext>, view: View) -> Layout {
+			let hints = self
+				.values
+				.iter()
+				.map(|x| x.height_hint(context, &view))
+				.collect();
+			let views = view
+				.clone()
+				.split_column(hints, self.justify, self.gap);
+
+			let children = self
+				.values
+				.iter()
+				.zip(views)
+				.map(|(w, v)| {
+					let width = v
+						.physical_width_hint(w.width_hint(context, &v))
+						.unwrap_or(v.width());
+					let (offset, width) = self
+						.align
+						.place(v.width(), width, None, 0);
+					let view = v.sub_view(
+						PhysicalPosition::new(offset, 0),
+						PhysicalSize::new(width, v.height()),
+					);
+					w.layout(context, view)
+				})
+				.collect();
+
+			Layout::new(view, children)
+		}
+
+		fn get_renderable(
+			&mut self,
+			context: &mut Context<WidgetContext>,
+			layout: &Layout,
+		) -> Self::Renderable {
+			self.values
+				.iter_mut()
+				.zip(layout.children())
+				.map(|(w, l)| w.get_renderable(context, l))
+				.collect()
+		}
+
+		fn width_hint(&self, context: &Context<LayoutContext>, view: &View) -> SizeHint {
+			SizeHint::Max(
+				self.values
+					.iter()
+					.map(|x| x.width_hint(context, view))
+					.collect(),
+			)
+		}
+
+		fn height_hint(&self, context: &Context<LayoutContext>, view: &View) -> SizeHint {
+			SizeHint::Sum(
+				self.values
+					.iter()
+					.map(|x| x.height_hint(context, view))
+					.chain([gaps(self.values.len(), self.gap)])
+					.collect(),
+			)
+		}
+
+		fn baseline(&self, context: &Context<LayoutContext>, view: &View) -> Option<u32> {
+			self.values
+				.first()?
+				.baseline(context, view)
+		}
+
+		fn handle(&mut self, event: &Event) {
+			for value in &mut self.values {
+				value.handle(event);
+			}
+		}
+
+		fn handle_pointer(&mut self, event: &PointerEvent, layout: &Layout) -> Propagation {
+			route(
+				event,
+				self.values
+					.iter_mut()
+					.zip(layout.children()),
+			)
+		}
+
+		fn visit_children<V: Visitor>(&mut self, layout: &Layout, visitor: &mut V) {
+			for (value, layout) in self
+				.values
+				.iter_mut()
+				.zip(layout.children())
+			{
+				visitor.visit(value, layout);
+			}
+		}
+	}
+
+	fn span(tracks: &[(u32, u32)], start: usize, end: usize) -> Option<(u32, u32)> {
+		let (first, _) = *tracks.get(start)?;
+		let (last, size) = tracks[..end.min(tracks.len())].last()?;
+		Some((first, last + size - first))
+	}
+
+	impl<T> Widget for Grid<T>
+	where
+		T: Widget,
+	{
+		type Renderable = Vec<T::Renderable>;
+
+		fn layout(&self, context: &Context<LayoutContext>, view: View) -> Layout {
+			let columns = view
+				.clone()
+				.split_row(self.column_hints(context, &view), Justify::Start, self.gap)
+				.into_iter()
+				.map(|x| (x.offset().x - view.offset().x, x.width()))
+				.collect::<Vec<_>>();
+			let rows = view
+				.clone()
+				.split_column(self.row_hints(context, &view), Justify::Start, self.gap)
+				.into_iter()
+				.map(|x| (x.offset().y - view.offset().y, x.height()))
+				.collect::<Vec<_>>();
+
+			let children = self
+				.cells
+				.iter()
+				.map(|cell| {
+					let column = span(&columns, cell.column, cell.column + cell.column_span);
+					let row = span(&rows, cell.row, cell.row + cell.row_span);
+
+					// Cells outside of the tracks of the grid are not shown.
+					let ((x, width), (y, height)) = column.zip(row).unwrap_or_default();
+
+					let cell_view = view.sub_view(
+						PhysicalPosition::new(x, y),
+						PhysicalSize::new(width, height),
+					);
+					cell.value
+						.layout(context, cell_view)
+				})
+				.collect();
+
+			Layout::new(view, children)
+		}
+
+		fn get_renderable(
+			&mut self,
+			context: &mut Context<WidgetContext>,
+			layout: &Layout,
+		) -> Self::Renderable {
+			self.cells
+				.iter_mut()
+				.zip(layout.children())
+				.map(|(w, l)| w.value.get_renderable(context, l))
+				.collect()
+		}
+
+		fn width_hint(&self, context: &Context<LayoutContext>, view: &View) -> SizeHint {
+			SizeHint::Sum(
+				self.column_hints(context, view)
+					.into_iter()
+					.chain([gaps(self.columns.len(), self.gap)])
+					.collect(),
+			)
+		}
+
+		fn height_hint(&self, context: &Context<LayoutContext>, view: &View) -> SizeHint {
+			SizeHint::Sum(
+				self.row_hints(context, view)
+					.into_iter()
+					.chain([gaps(self.rows.len(), self.gap)])
+					.collect(),
+			)
+		}
+
+		fn handle(&mut self, event: &Event) {
+			for cell in &mut self.cells {
+				cell.value.handle(event);
+			}
+		}
+
+		fn handle_pointer(&mut self, event: &PointerEvent, layout: &Layout) -> Propagation {
+			let cells = self
+				.cells
+				.iter_mut()
+				.map(|x| &mut x.value);
+			route(event, cells.zip(layout.children()))
+		}
+
+		fn visit_children<V: Visitor>(&mut self, layout: &Layout, visitor: &mut V) {
+			for (cell, layout) in self
+				.cells
+				.iter_mut()
+				.zip(layout.children())
+			{
+				visitor.visit(&mut cell.value, layout);
+			}
+		}
+	}
+
+	fn shift(align: Align, available: u32, size: u32, shift: i32) -> (u32, u32) {
+		let (offset, size) = align.place(available, size, None, 0);
+		let offset = offset.saturating_add_signed(shift);
+		(offset.min(available - size), size)
+	}
+
+	impl<T> Widget for Stack<T>
+	where
+		T: Widget,
+	{
+		type Renderable = Vec<T::Renderable>;
+
+		fn layout(&self, context: &Context<LayoutContext>, view: View) -> Layout {
+			let children = self
+				.layers
+				.iter()
+				.map(|layer| {
+					let size = view
+						.clone()
+						.from_size_hints(
+							layer.width_hint(context, &view),
+							layer.height_hint(context, &view),
+						)
+						.size();
+
+					let (horizontal, vertical) = layer.anchor.alignment();
+					let (x, width) = shift(horizontal, view.width(), size.width, layer.offset.0);
+					let (y, height) = shift(vertical, view.height(), size.height, layer.offset.1);
+
+					layer.value.layout(
+						context,
+						view.sub_view(
+							PhysicalPosition::new(x, y),
+							PhysicalSize::new(width, height),
+						),
+					)
+				})
+				.collect();
+
+			Layout::new(view, children)
+		}
+
+		fn get_renderable(
+			&mut self,
+			context: &mut Context<WidgetContext>,
+			layout: &Layout,
+		) -> Self::Renderable {
+			let mut renderables = self
+				.layers
+				.iter_mut()
+				.zip(layout.children())
+				.map(|(w, l)| (w.z_index, w.value.get_renderable(context, l)))
+				.collect::<Vec<_>>();
+
+			// The sort is stable, so layers with the same index keep the order they were added in.
+			renderables.sort_by_key(|(z_index, _)| *z_index);
+
+			renderables
+				.into_iter()
+				.map(|(_, renderable)| renderable)
+				.collect()
+		}
+
+		fn width_hint(&self, context: &Context<LayoutContext>, view: &View) -> SizeHint {
+			SizeHint::Max(
+				self.layers
+					.iter()
+					.map(|x| x.width_hint(context, view))
+					.collect(),
+			)
+		}
+
+		fn height_hint(&self, context: &Context<LayoutContext>, view: &View) -> SizeHint {
+			SizeHint::Max(
+				self.layers
+					.iter()
+					.map(|x| x.height_hint(context, view))
+					.collect(),
+			)
+		}
+
+		fn handle(&mut self, event: &Event) {
+			for layer in &mut self.layers {
+				layer.value.handle(event);
+			}
+		}
+
+		fn handle_pointer(&mut self, event: &PointerEvent, layout: &Layout) -> Propagation {
+			let mut layers = self
+				.layers
+				.iter_mut()
+				.zip(layout.children())
+				.collect::<Vec<_>>();
+			layers.sort_by_key(|(layer, _)| layer.z_index);
+
+			let layers = layers
+				.into_iter()
+				.map(|(layer, layout)| (&mut layer.value, layout));
+			route(event, layers)
+		}
+
+		fn visit_children<V: Visitor>(&mut self, layout: &Layout, visitor: &mut V) {
+			for (layer, layout) in self
+				.layers
+				.iter_mut()
+				.zip(layout.children())
+			{
+				visitor.visit(&mut layer.value, layout);
+			}
+		}
+	}
+
+	impl<T> Widget for Bordered<T>
+	where
+		T: Widget,
+	{
+		type Renderable = (RenderedShape, T::Renderable);
+
+		fn layout(&self, context: &Context<LayoutContext>, view: View) -> Layout {
+			let width = self.width_hint(context, &view);
+			let height = self.height_hint(context, &view);
+			let view = view.from_size_hints(width, height);
+			let (outer, inner) = view.bordered(self.size);
+
+			Layout::new(outer, vec![self.value.layout(context, inner)])
+		}
+
+		fn get_renderable(
+			&mut self,
+			context: &mut Context<WidgetContext>,
+			layout: &Layout,
+		) -> Self::Renderable {
+			let border = Shape::new(Color::TRANSPARENT)
+				.rounded(self.radii)
+				.outlined(self.size as f32, self.color);
+			let border = shaped(layout.view(), &border);
+
+			(
+				border,
+				self.value
+					.get_renderable(context, &layout.children()[0]),
+			)
+		}
+
+		fn width_hint(&self, context: &Context<LayoutContext>, view: &View) -> SizeHint {
+			SizeHint::Sum(vec![
+				self.value
+					.width_hint(context, view),
+				SizeHint::Physical(self.size * 2),
+			])
+		}
+
+		fn height_hint(&self, context: &Context<LayoutContext>, view: &View) -> SizeHint {
+			SizeHint::Sum(vec![
+				self.value
+					.height_hint(context, view),
+				SizeHint::Physical(self.size * 2),
+			])
+		}
+
+		fn baseline(&self, context: &Context<LayoutContext>, view: &View) -> Option<u32> {
+			self.value
+				.baseline(context, view)
+				.map(|x| x + self.size)
+		}
+
+		fn resize(&mut self, new_size: PhysicalSize<u32>) {
+			self.value
+				.resize(PhysicalSize::new(
+					new_size
+						.width
+						.saturating_sub(self.size * 2),
+					new_size
+						.height
+						.saturating_sub(self.size * 2),
+				));
+		}
+
+		fn handle(&mut self, event: &Event) {
+			self.value.handle(event);
+		}
+
+		fn handle_pointer(&mut self, event: &PointerEvent, layout: &Layout) -> Propagation {
+			let child = (&mut self.value, &layout.children()[0]);
+			route(event, std::iter::once(child))
+		}
+
+		fn visit_children<V: Visitor>(&mut self, layout: &Layout, visitor: &mut V) {
+			visitor.visit(&mut self.value, &layout.children()[0]);
+		}
+	}
+
+	impl Widget for Rect {
+		type Renderable = RenderedShape;
+
+		fn layout(&self, _context: &Context<LayoutContext>, view: View) -> Layout {
+			Layout::leaf(view)
+		}
+
+		fn get_renderable(
+			&mut self,
+			_context: &mut Context<WidgetContext>,
+			layout: &Layout,
+		) -> Self::Renderable {
+			shaped(layout.view(), &self.shape)
+		}
+	}
+
+	impl<T> Widget for Background<T>
+	where
+		T: Widget,
+	{
+		type Renderable = (RenderedShape, T::Renderable);
+
+		fn layout(&self, context: &Context<LayoutContext>, view: View) -> Layout {
+			let width = self.width_hint(context, &view);
+			let height = self.height_hint(context, &view);
+			let view = view.from_size_hints(width, height);
+
+			Layout::new(view.clone(), vec![self.value.layout(context, view)])
+		}
+
+		fn get_renderable(
+			&mut self,
+			context: &mut Context<WidgetContext>,
+			layout: &Layout,
+		) -> Self::Renderable {
+			let background = Shape::new(self.fill.clone()).rounded(self.radii);
+			(
+				shaped(layout.view(), &background),
+				self.value
+					.get_renderable(context, &layout.children()[0]),
+			)
+		}
+
+		fn width_hint(&self, context: &Context<LayoutContext>, view: &View) -> SizeHint {
+			self.value
+				.width_hint(context, view)
+		}
+
+		fn height_hint(&self, context: &Context<LayoutContext>, view: &View) -> SizeHint {
+			self.value
+				.height_hint(context, view)
+		}
+
+		fn baseline(&self, context: &Context<LayoutContext>, view: &View) -> Option<u32> {
+			self.value.baseline(context, view)
+		}
+
+		fn resize(&mut self, new_size: PhysicalSize<u32>) {
+			self.value.resize(new_size);
+		}
+
+		fn handle(&mut self, event: &Event) {
+			self.value.handle(event);
+		}
+
+		fn handle_pointer(&mut self, event: &PointerEvent, layout: &Layout) -> Propagation {
+			let child = (&mut self.value, &layout.children()[0]);
+			route(event, std::iter::once(child))
+		}
+
+		fn visit_children<V: Visitor>(&mut self, layout: &Layout, visitor: &mut V) {
+			visitor.visit(&mut self.value, &layout.children()[0]);
+		}
+	}
+
+	impl<T> Widget for Shadow<T>
+	where
+		T: Widget,
+	{
+		type Renderable = (RenderedShape, T::Renderable);
+
+		fn layout(&self, context: &Context<LayoutContext>, view: View) -> Layout {
+			let width = self.width_hint(context, &view);
+			let height = self.height_hint(context, &view);
+			let view = view.from_size_hints(width, height);
+
+			Layout::new(view.clone(), vec![self.value.layout(context, view)])
+		}
+
+		fn get_renderable(
+			&mut self,
+			context: &mut Context<WidgetContext>,
+			layout: &Layout,
+		) -> Self::Renderable {
+			let (vertices, indices) = self.shadow.mesh(layout.view());
+			(
+				RenderedShape::new(vertices, indices),
+				self.value
+					.get_renderable(context, &layout.children()[0]),
+			)
+		}
+
+		fn width_hint(&self, context: &Context<LayoutContext>, view: &View) -> SizeHint {
+			self.value
+				.width_hint(context, view)
+		}
+
+		fn height_hint(&self, context: &Context<LayoutContext>, view: &View) -> SizeHint {
+			self.value
+				.height_hint(context, view)
+		}
+
+		fn baseline(&self, context: &Context<LayoutContext>, view: &View) -> Option<u32> {
+			self.value.baseline(context, view)
+		}
+
+		fn resize(&mut self, new_size: PhysicalSize<u32>) {
+			self.value.resize(new_size);
+		}
+
+		fn handle(&mut self, event: &Event) {
+			self.value.handle(event);
+		}
+
+		fn handle_pointer(&mut self, event: &PointerEvent, layout: &Layout) -> Propagation {
+			let child = (&mut self.value, &layout.children()[0]);
+			route(event, std::iter::once(child))
+		}
+
+		fn visit_children<V: Visitor>(&mut self, layout: &Layout, visitor: &mut V) {
+			visitor.visit(&mut self.value, &layout.children()[0]);
+		}
+	}
+
+	impl<T> Widget for TextStyle<T>
+	where
+		T: Widget,
+	{
+		type Renderable = T::Renderable;
+
+		fn layout(&self, context: &Context<LayoutContext>, view: View) -> Layout {
+			self.value
+				.layout(&Context::new(self.styled(context)), view)
+		}
+
+		fn prepare(&mut self, layout: &Layout) -> bool {
+			self.value.prepare(layout)
+		}
+
+		fn changed(&mut self, layout: &Layout) -> bool {
+			self.value.changed(layout)
+		}
+
+		fn get_renderable(
+			&mut self,
+			context: &mut Context<WidgetContext>,
+			layout: &Layout,
+		) -> Self::Renderable {
+			let styled = self.styled(&context.layout);
+			let previous_layout = std::mem::replace(&mut context.layout, styled);
+			let color = self
+				.color
+				.unwrap_or(context.text_color);
+			let previous_color = std::mem::replace(&mut context.text_color, color);
+			let renderable = self
+				.value
+				.get_renderable(context, layout);
+			context.layout = previous_layout;
+			context.text_color = previous_color;
+			renderable
+		}
+
+		fn width_hint(&self, context: &Context<LayoutContext>, view: &View) -> SizeHint {
+			self.value
+				.width_hint(&Context::new(self.styled(context)), view)
+		}
+
+		fn height_hint(&self, context: &Context<LayoutContext>, view: &View) -> SizeHint {
+			self.value
+				.height_hint(&Context::new(self.styled(context)), view)
+		}
+
+		fn baseline(&self, context: &Context<LayoutContext>, view: &View) -> Option<u32> {
+			self.value
+				.baseline(&Context::new(self.styled(context)), view)
+		}
+
+		fn resize(&mut self, new_size: PhysicalSize<u32>) {
+			self.value.resize(new_size);
+		}
+
+		fn handle(&mut self, event: &Event) {
+			self.value.handle(event);
+		}
+
+		fn handle_pointer(&mut self, event: &PointerEvent, layout: &Layout) -> Propagation {
+			self.value
+				.handle_pointer(event, layout)
+		}
+
+		fn focusable(&self) -> bool {
+			self.value.focusable()
+		}
+
+		fn focus_changed(&mut self, focused: bool) {
+			self.value.focus_changed(focused);
 		}
 
-		fn width_hint(&self, context: &Context<WidgetContext>, view: &View) -> SizeHint {
-			WrappingRow::new(self.chars().collect()).width_hint(context, view)
+		fn handle_key(&mut self, event: &Event) {
+			self.value.handle_key(event);
 		}
 
-		fn height_hint(&self, context: &Context<WidgetContext>, view: &View) -> SizeHint {
-			WrappingRow::new(self.chars().collect()).height_hint(context, view)
+		fn visit_children<V: Visitor>(&mut self, layout: &Layout, visitor: &mut V) {
+			self.value
+				.visit_children(layout, visitor);
+		}
+	}
+
+	impl<T> TextStyle<T> {
+		fn styled<'a>(&self, context: &LayoutContext<'a>) -> LayoutContext<'a> {
+			let context = context.with_wrap(
+				self.wrap.unwrap_or(context.wrap()),
+				self.max_lines
+					.or(context.max_lines()),
+			);
+			match self.size {
+				Some(size) => context.with_font_size(size),
+				None => context,
+			}
 		}
 	}
 
-	impl<'a, T> Widget for &'a mut T
+	impl<T> Widget for Padded<T>
 	where
 		T: Widget,
 	{
 		type Renderable = T::Renderable;
 
+		fn layout(&self, context: &Context<LayoutContext>, view: View) -> Layout {
+			let inner = view.inset(self.top, self.right, self.bottom, self.left);
+			Layout::new(view, vec![self.value.layout(context, inner)])
+		}
+
 		fn get_renderable(
 			&mut self,
 			context: &mut Context<WidgetContext>,
-			view: View,
+			layout: &Layout,
 		) -> Self::Renderable {
-			(**self).get_renderable(context, view)
+			self.value
+				.get_renderable(context, &layout.children()[0])
+		}
+
+		fn width_hint(&self, context: &Context<LayoutContext>, view: &View) -> SizeHint {
+			SizeHint::Sum(vec![
+				self.value
+					.width_hint(context, view),
+				SizeHint::Physical(self.left + self.right),
+			])
+		}
+
+		fn height_hint(&self, context: &Context<LayoutContext>, view: &View) -> SizeHint {
+			SizeHint::Sum(vec![
+				self.value
+					.height_hint(context, view),
+				SizeHint::Physical(self.top + self.bottom),
+			])
+		}
+
+		fn baseline(&self, context: &Context<LayoutContext>, view: &View) -> Option<u32> {
+			self.value
+				.baseline(context, view)
+				.map(|x| x + self.top)
 		}
 
 		fn resize(&mut self, new_size: PhysicalSize<u32>) {
-			(**self).resize(new_size);
+			self.value
+				.resize(PhysicalSize::new(
+					new_size
+						.width
+						.saturating_sub(self.left + self.right),
+					new_size
+						.height
+						.saturating_sub(self.top + self.bottom),
+				));
 		}
 
-		fn handle(&mut self, event: &WindowEvent) {
-			(**self).handle(event);
+		fn handle(&mut self, event: &Event) {
+			self.value.handle(event);
 		}
 
-		fn width_hint(&self, context: &Context<WidgetContext>, view: &View) -> SizeHint {
-			(**self).width_hint(context, view)
+		fn handle_pointer(&mut self, event: &PointerEvent, layout: &Layout) -> Propagation {
+			let child = (&mut self.value, &layout.children()[0]);
+			route(event, std::iter::once(child))
 		}
 
-		fn height_hint(&self, context: &Context<WidgetContext>, view: &View) -> SizeHint {
-			(**self).height_hint(context, view)
+		fn visit_children<V: Visitor>(&mut self, layout: &Layout, visitor: &mut V) {
+			visitor.visit(&mut self.value, &layout.children()[0]);
 		}
 	}
 
-	impl<T> Widget for Row<T>
+	impl<T> Widget for Flexible<T>
 	where
 		T: Widget,
 	{
-		type Renderable = Vec<T::Renderable>;
+		type Renderable = T::Renderable;
+
+		fn layout(&self, context: &Context<LayoutContext>, view: View) -> Layout {
+			self.value.layout(context, view)
+		}
 
 		fn get_renderable(
 			&mut self,
 			context: &mut Context<WidgetContext>,
-			view: View,
+			layout: &Layout,
 		) -> Self::Renderable {
-			let width = self.width_hint(context, &view);
-			let height = self.height_hint(context, &view);
-			let view = view.from_size_hints(width, height);
+			self.value
+				.get_renderable(context, layout)
+		}
 
-			let hints = self
-				.values
-				.iter()
-				.map(|x| x.width_hint(context, &view))
-				.collect();
-			let views = view.split_row(hints);
+		fn width_hint(&self, context: &Context<LayoutContext>, view: &View) -> SizeHint {
+			let hint = self
+				.value
+				.width_hint(context, view);
+			match self.width {
+				Some(weight) => SizeHint::Max(vec![hint, SizeHint::Flex(weight)]),
+				None => hint,
+			}
+		}
 
-			self.values
-				.iter_mut()
-				.zip(views)
-				.map(|(w, v)| w.get_renderable(context, v))
-				.collect()
+		fn height_hint(&self, context: &Context<LayoutContext>, view: &View) -> SizeHint {
+			let hint = self
+				.value
+				.height_hint(context, view);
+			match self.height {
+				Some(weight) => SizeHint::Max(vec![hint, SizeHint::Flex(weight)]),
+				None => hint,
+			}
 		}
 
-		fn width_hint(&self, context: &Context<WidgetContext>, view: &View) -> SizeHint {
-			SizeHint::Sum(
-				self.values
-					.iter()
-					.map(|x| x.width_hint(context, &view))
-					.collect(),
-			)
+		fn baseline(&self, context: &Context<LayoutContext>, view: &View) -> Option<u32> {
+			self.value.baseline(context, view)
 		}
 
-		fn height_hint(&self, context: &Context<WidgetContext>, view: &View) -> SizeHint {
-			SizeHint::Max(
-				self.values
-					.iter()
-					.map(|x| x.height_hint(context, &view))
-					.collect(),
-			)
+		fn resize(&mut self, new_size: PhysicalSize<u32>) {
+			self.value.resize(new_size);
 		}
 
-		fn handle(&mut self, event: &WindowEvent) {
-			for value in &mut self.values {
-				value.handle(event);
-			}
+		fn handle(&mut self, event: &Event) {
+			self.value.handle(event);
+		}
+
+		fn handle_pointer(&mut self, event: &PointerEvent, layout: &Layout) -> Propagation {
+			self.value
+				.handle_pointer(event, layout)
+		}
+
+		fn visit_children<V: Visitor>(&mut self, layout: &Layout, visitor: &mut V) {
+			visitor.visit(&mut self.value, layout);
 		}
 	}
 
-	impl<T> Widget for WrappingRow<T>
+	fn wheel_delta(delta: &MouseScrollDelta) -> (f32, f32) {
+		// Scrolling the wheel up moves the content down, so back towards the start.
+		match delta {
+			MouseScrollDelta::LineDelta(x, y) => (-x * SCROLL_LINE, -y * SCROLL_LINE),
+			MouseScrollDelta::PixelDelta(delta) => (-delta.x as f32, -delta.y as f32),
+		}
+	}
+
+	impl<T> Widget for Scroll<T>
 	where
 		T: Widget,
 	{
-		type Renderable = Vec<Vec<<T as Widget>::Renderable>>;
+		type Renderable = Clipped<T::Renderable>;
+
+		fn layout(&self, context: &Context<LayoutContext>, view: View) -> Layout {
+			let content = self.content_size(context, &view);
+			let offset = PhysicalPosition::new(
+				(self.offset.x as u32).min(content.width - view.width()),
+				(self.offset.y as u32).min(content.height - view.height()),
+			);
+
+			let child = self
+				.value
+				.layout(context, view.scrolled(offset, content));
+			Layout::new(view, vec![child])
+		}
 
 		fn get_renderable(
 			&mut self,
 			context: &mut Context<WidgetContext>,
-			view: View,
+			layout: &Layout,
 		) -> Self::Renderable {
-			let mut columns = vec![];
-			let mut current_row = vec![];
-
-			let mut offset = 0;
-			for value in &mut self.values {
-				offset += view
-					.physical_width_hint(value.width_hint(context, &view))
-					.unwrap_or(0);
-
-				if offset > view.width() {
-					columns.push(Row::new(std::mem::take(&mut current_row)));
-					offset = 0;
-				}
+			Clipped::new(
+				layout.view(),
+				self.value
+					.get_renderable(context, &layout.children()[0]),
+			)
+		}
 
-				current_row.push(value);
+		fn width_hint(&self, context: &Context<LayoutContext>, view: &View) -> SizeHint {
+			if self.axis.horizontal() {
+				SizeHint::None
+			} else {
+				self.value
+					.width_hint(context, view)
 			}
+		}
 
-			if !current_row.is_empty() {
-				columns.push(Row::new(current_row));
+		fn height_hint(&self, context: &Context<LayoutContext>, view: &View) -> SizeHint {
+			if self.axis.vertical() {
+				SizeHint::None
+			} else {
+				self.value
+					.height_hint(context, view)
 			}
-
-			Column::new(columns).get_renderable(context, view)
 		}
 
-		fn handle(&mut self, event: &WindowEvent) {
-			for value in &mut self.values {
-				value.handle(event);
-			}
+		fn resize(&mut self, new_size: PhysicalSize<u32>) {
+			self.value.resize(new_size);
 		}
 
-		fn width_hint(&self, context: &Context<WidgetContext>, view: &View) -> SizeHint {
-			let sum = self
-				.values
-				.iter()
-				.map(|x| x.width_hint(context, &view))
-				.collect();
-			SizeHint::Min(vec![SizeHint::Sum(sum), SizeHint::Physical(view.width())])
+		fn handle(&mut self, event: &Event) {
+			self.value.handle(event);
 		}
 
-		fn height_hint(&self, context: &Context<WidgetContext>, view: &View) -> SizeHint {
-			let heights = self
-				.values
-				.iter()
-				.peekable()
-				.batching(|x| {
-					let mut width = 0;
-					let mut heights = vec![];
-					while let Some(val) = x.peek() {
-						width += view
-							.physical_width_hint(val.width_hint(context, view))
-							.unwrap_or(0);
-						if width > view.width() {
-							break;
-						}
-
-						heights.push(val.height_hint(context, view));
-						x.next();
-					}
+		fn handle_pointer(&mut self, event: &PointerEvent, layout: &Layout) -> Propagation {
+			let child = (&mut self.value, &layout.children()[0]);
+			if route(event, std::iter::once(child)) == Propagation::Stop {
+				return Propagation::Stop;
+			}
 
-					if heights.is_empty() {
-						None
-					} else {
-						Some(heights)
-					}
-				});
-			SizeHint::Sum(
-				heights
-					.map(|x| SizeHint::Max(x))
-					.collect(),
-			)
+			match event.kind() {
+				PointerKind::Wheel(delta) => {
+					// The offset may be past the end after `scroll_to` or when the value shrunk, so
+					// it is limited by the layout the event happened in.
+					let (x, y) = wheel_delta(&delta);
+					let max = Self::max_offset(layout);
+					self.scroll_to(PhysicalPosition::new(
+						(self.offset.x.min(max.x) + x).min(max.x),
+						(self.offset.y.min(max.y) + y).min(max.y),
+					));
+					Propagation::Stop
+				}
+				_ => Propagation::Continue,
+			}
+		}
+
+		fn visit_children<V: Visitor>(&mut self, layout: &Layout, visitor: &mut V) {
+			visitor.visit(&mut self.value, &layout.children()[0]);
 		}
 	}
 
-	impl<T> Widget for Column<T>
+	impl<T, F> Widget for VirtualList<T, F>
 	where
 		T: Widget,
+		F: FnMut(usize, Option<T>) -> T,
 	{
-		type Renderable = Vec<T::Renderable>;
+		type Renderable = Clipped<Vec<T::Renderable>>;
+
+		fn layout(&self, context: &Context<LayoutContext>, view: View) -> Layout {
+			let offset = self.clamped_offset(view.height());
+			let content = view.scrolled(
+				PhysicalPosition::new(0, offset),
+				PhysicalSize::new(
+					view.width(),
+					self.content_height()
+						.max(view.height()),
+				),
+			);
+
+			let children = self
+				.items
+				.iter()
+				.map(|(index, item)| {
+					let item_view = content.sub_view(
+						PhysicalPosition::new(0, *index as u32 * self.item_height),
+						PhysicalSize::new(view.width(), self.item_height),
+					);
+					item.layout(context, item_view)
+				})
+				.collect();
+
+			Layout::new(view, children)
+		}
+
+		fn prepare(&mut self, layout: &Layout) -> bool {
+			// The children were laid out as they were before, so they are only prepared once
+			// they have been laid out again.
+			if self.update(self.visible(layout.view().height()), layout) {
+				return true;
+			}
+
+			self.items
+				.iter_mut()
+				.zip(layout.children())
+				.fold(false, |changed, ((_, item), layout)| {
+					item.prepare(layout) || changed
+				})
+		}
 
 		fn get_renderable(
 			&mut self,
 			context: &mut Context<WidgetContext>,
-			view: View,
+			layout: &Layout,
 		) -> Self::Renderable {
-			let width = self.width_hint(context, &view);
-			let height = self.height_hint(context, &view);
-			let view = view.from_size_hints(width, height);
-
-			let hints = self
-				.values
-				.iter()
-				.map(|x| x.height_hint(context, &view))
+			let renderables = self
+				.items
+				.iter_mut()
+				.zip(layout.children())
+				.map(|((_, w), l)| w.get_renderable(context, l))
 				.collect();
-			let views = view.split_column(hints);
 
-			self.values
-				.iter_mut()
-				.zip(views)
-				.map(|(w, v)| w.get_renderable(context, v))
-				.collect()
+			Clipped::new(layout.view(), renderables)
 		}
 
-		fn width_hint(&self, context: &Context<WidgetContext>, view: &View) -> SizeHint {
-			SizeHint::Max(
-				self.values
-					.iter()
-					.map(|x| x.width_hint(context, view))
-					.collect(),
-			)
+		fn resize(&mut self, new_size: PhysicalSize<u32>) {
+			for (_, item) in &mut self.items {
+				item.resize(new_size);
+			}
 		}
 
-		fn height_hint(&self, context: &Context<WidgetContext>, view: &View) -> SizeHint {
-			SizeHint::Sum(
-				self.values
-					.iter()
-					.map(|x| x.height_hint(context, view))
-					.collect(),
-			)
+		fn handle(&mut self, event: &Event) {
+			for (_, item) in &mut self.items {
+				item.handle(event);
+			}
 		}
 
-		fn handle(&mut self, event: &WindowEvent) {
-			for value in &mut self.values {
-				value.handle(event);
+		fn handle_pointer(&mut self, event: &PointerEvent, layout: &Layout) -> Propagation {
+			let items = self
+				.items
+				.iter_mut()
+				.map(|(_, item)| item);
+			if route(event, items.zip(layout.children())) == Propagation::Stop {
+				return Propagation::Stop;
+			}
+
+			match event.kind() {
+				PointerKind::Wheel(delta) => {
+					let max = self
+						.content_height()
+						.saturating_sub(layout.view().height()) as f32;
+					self.scroll_to((self.offset.min(max) + wheel_delta(&delta).1).min(max));
+					Propagation::Stop
+				}
+				_ => Propagation::Continue,
+			}
+		}
+
+		fn visit_children<V: Visitor>(&mut self, layout: &Layout, visitor: &mut V) {
+			for ((index, item), layout) in self
+				.items
+				.iter_mut()
+				.zip(layout.children())
+			{
+				visitor.visit_keyed(*index, item, layout);
 			}
 		}
 	}
 
-	impl<T> Widget for Bordered<T>
+	impl<T> Widget for Cached<T>
 	where
 		T: Widget,
 	{
-		type Renderable = (RenderedMesh, T::Renderable);
+		type Renderable = std::rc::Rc<T::Renderable>;
+
+		fn layout(&self, context: &Context<LayoutContext>, view: View) -> Layout {
+			self.value.layout(context, view)
+		}
+
+		fn prepare(&mut self, layout: &Layout) -> bool {
+			self.value.prepare(layout)
+		}
 
 		fn get_renderable(
 			&mut self,
 			context: &mut Context<WidgetContext>,
-			view: View,
+			layout: &Layout,
 		) -> Self::Renderable {
-			let width = self.width_hint(context, &view);
-			let height = self.height_hint(context, &view);
-			let view = view.from_size_hints(width, height);
-			let (outer, inner) = view.bordered(self.size);
-
-			let size = wgpu::Extent3d {
-				width: 10,
-				height: 10,
-				depth_or_array_layers: 1,
-			};
-
-			let mut texture = Texture::new(context.device, size, context.config.format);
-
-			let data = vec![[10, 10, 10, 255]; 10 * 10]
-				.into_iter()
-				.flatten()
-				.collect::<Vec<_>>();
-
-			texture.write_data(context.queue, &data);
-
-			let bind_group =
-				texture.bind_group(context.device, context.bind_group_layout, context.sampler);
-
-			let mut vertices = outer.corners().to_vec();
-			vertices.extend(inner.corners());
-
-			let mut indices = vec![];
-
-			indices.extend([0, 1, 4]);
-			indices.extend([1, 5, 4]);
-
-			indices.extend([0, 4, 3]);
-			indices.extend([4, 7, 3]);
+			// The renderable can't be used anymore when glyphs it draws have been evicted, when
+			// the value is laid out differently or when it says it changed.
+			let changed = self.value.changed(layout);
+			match self.cached {
+				Some((ref renderable, evictions, ref cached))
+					if !changed && evictions == context.evictions() && cached == layout =>
+				{
+					renderable.clone()
+				}
+				_ => {
+					let renderable = std::rc::Rc::new(
+						self.value
+							.get_renderable(context, layout),
+					);
+					self.cached = Some((renderable.clone(), context.evictions(), layout.clone()));
+					renderable
+				}
+			}
+		}
 
-			indices.extend([3, 7, 6]);
-			indices.extend([3, 6, 2]);
+		fn changed(&mut self, layout: &Layout) -> bool {
+			self.cached.is_none() || self.value.changed(layout)
+		}
 
-			indices.extend([1, 6, 5]);
-			indices.extend([1, 2, 6]);
+		fn width_hint(&self, context: &Context<LayoutContext>, view: &View) -> SizeHint {
+			self.value
+				.width_hint(context, view)
+		}
 
-			let border = RenderedMesh::new(&context.device, &vertices, &indices, bind_group);
+		fn height_hint(&self, context: &Context<LayoutContext>, view: &View) -> SizeHint {
+			self.value
+				.height_hint(context, view)
+		}
 
-			(
-				border,
-				self.value
-					.get_renderable(context, inner),
-			)
+		fn baseline(&self, context: &Context<LayoutContext>, view: &View) -> Option<u32> {
+			self.value.baseline(context, view)
 		}
 
-		fn width_hint(&self, context: &Context<WidgetContext>, view: &View) -> SizeHint {
-			SizeHint::Sum(vec![
-				self.value
-					.width_hint(context, view),
-				SizeHint::Physical(self.size * 2),
-			])
+		fn resize(&mut self, new_size: PhysicalSize<u32>) {
+			self.value.resize(new_size);
 		}
 
-		fn height_hint(&self, context: &Context<WidgetContext>, view: &View) -> SizeHint {
-			SizeHint::Sum(vec![
-				self.value
-					.height_hint(context, view),
-				SizeHint::Physical(self.size * 2),
-			])
+		// Events go to the value without throwing the cached renderable away, the value reports
+		// whether they changed it through `changed`.
+		fn handle(&mut self, event: &Event) {
+			self.value.handle(event);
 		}
 
-		fn resize(&mut self, new_size: PhysicalSize<u32>) {
+		fn handle_pointer(&mut self, event: &PointerEvent, layout: &Layout) -> Propagation {
 			self.value
-				.resize(PhysicalSize::new(
-					new_size.width - self.size * 2,
-					new_size.height - self.size * 2,
-				));
+				.handle_pointer(event, layout)
 		}
-	}
-
-	impl<T> Widget for Cached<T>
-	where
-		T: Widget,
-	{
-		type Renderable = std::rc::Rc<T::Renderable>;
 
-		fn get_renderable(
-			&mut self,
-			context: &mut Context<WidgetContext>,
-			view: View,
-		) -> Self::Renderable {
-			if let Some(ref renderable) = self.cached {
-				renderable.clone()
-			} else {
-				let renderable = std::rc::Rc::new(
-					self.value
-						.get_renderable(context, view),
-				);
-				self.cached = Some(renderable.clone());
-				renderable
-			}
+		fn focusable(&self) -> bool {
+			self.value.focusable()
 		}
 
-		fn width_hint(&self, context: &Context<WidgetContext>, view: &View) -> SizeHint {
-			(**self).width_hint(context, view)
+		fn focus_changed(&mut self, focused: bool) {
+			self.value.focus_changed(focused);
 		}
 
-		fn height_hint(&self, context: &Context<WidgetContext>, view: &View) -> SizeHint {
-			(**self).height_hint(context, view)
+		fn handle_key(&mut self, event: &Event) {
+			self.value.handle_key(event);
 		}
 
-		fn resize(&mut self, new_size: PhysicalSize<u32>) {
-			(**self).resize(new_size);
+		fn visit_children<V: Visitor>(&mut self, layout: &Layout, visitor: &mut V) {
+			self.value
+				.visit_children(layout, visitor);
 		}
 	}
 
@@ -590,36 +3120,76 @@ mod impls {
         impl<$($name: Widget),*> Widget for ($($name),*) {
         	type Renderable = ($($name::Renderable),*);
 
-        	fn get_renderable(&mut self, context: &mut crate::context::Context<WidgetContext>, view: crate::view::View) -> Self::Renderable {
+        	fn layout(&self, context: &crate::context::Context<crate::layout::LayoutContext>, view: crate::view::View) -> crate::layout::Layout {
         		let width = self.width_hint(context, &view);
         		let height = self.height_hint(context, &view);
         		let view = view.from_size_hints(width, height);
     			paste! {
     				let ($([<$name:snake>]),*) = self;
-    				($(<$name as Widget>::get_renderable([<$name:snake>], context, view.clone())),*)
+    				let children = vec![$(<$name as Widget>::layout([<$name:snake>], context, view.clone())),*];
+    			}
+    			crate::layout::Layout::new(view, children)
+    		}
+
+        	fn get_renderable(&mut self, context: &mut crate::context::Context<WidgetContext>, layout: &crate::layout::Layout) -> Self::Renderable {
+    			paste! {
+    				let ($([<$name:snake>]),*) = self;
+    				let mut children = layout.children().iter();
+    				($(<$name as Widget>::get_renderable([<$name:snake>], context, children.next().unwrap())),*)
     			}
     		}
 
-    		fn width_hint(&self, context: &crate::context::Context<crate::widget::WidgetContext>, view: &crate::view::View) -> crate::view::SizeHint {
+    		fn width_hint(&self, context: &crate::context::Context<crate::layout::LayoutContext>, view: &crate::view::View) -> crate::view::SizeHint {
     			paste! {
     				let ($([<$name:snake>]),*) = self;
     				crate::view::SizeHint::Max(vec![$(<$name as Widget>::width_hint([<$name:snake>], context, view)),*])
     			}
     		}
 
-    		fn height_hint(&self, context: &crate::context::Context<crate::widget::WidgetContext>, view: &crate::view::View) -> crate::view::SizeHint {
+    		fn height_hint(&self, context: &crate::context::Context<crate::layout::LayoutContext>, view: &crate::view::View) -> crate::view::SizeHint {
     			paste! {
     				let ($([<$name:snake>]),*) = self;
     				crate::view::SizeHint::Max(vec![$(<$name as Widget>::height_hint([<$name:snake>], context, view)),*])
     			}
     		}
 
-			fn resize(&mut self, new_size: winit::dpi::PhysicalSize<u32>) {
+    		fn baseline(&self, context: &crate::context::Context<crate::layout::LayoutContext>, view: &crate::view::View) -> Option<u32> {
+    			paste! {
+    				let ($([<$name:snake>]),*) = self;
+    				[$(<$name as Widget>::baseline([<$name:snake>], context, view)),*].into_iter().flatten().max()
+    			}
+    		}
+
+			fn resize(&mut self, new_size: crate::view::PhysicalSize<u32>) {
 				paste! {
     				let ($([<$name:snake>]),*) = self;
     				$(<$name as Widget>::resize([<$name:snake>], new_size);)*
 				}
 			}
+
+			fn handle(&mut self, event: &crate::event::Event) {
+				paste! {
+    				let ($([<$name:snake>]),*) = self;
+    				$(<$name as Widget>::handle([<$name:snake>], event);)*
+				}
+			}
+
+			fn handle_pointer(&mut self, event: &crate::event::PointerEvent, layout: &crate::layout::Layout) -> crate::event::Propagation {
+				paste! {
+    				let ($([<$name:snake>]),*) = self;
+    				let mut children = layout.children().iter();
+    				let targets: Vec<(&mut dyn crate::event::PointerTarget, &crate::layout::Layout)> = vec![$(([<$name:snake>], children.next().unwrap())),*];
+				}
+				crate::event::route(event, targets.into_iter())
+			}
+
+			fn visit_children<V: Visitor>(&mut self, layout: &crate::layout::Layout, visitor: &mut V) {
+				paste! {
+    				let ($([<$name:snake>]),*) = self;
+    				let mut children = layout.children().iter();
+    				$(visitor.visit([<$name:snake>], children.next().unwrap());)*
+				}
+			}
         }
     };
 }
@@ -1,11 +1,17 @@
 use inner::WindowInner;
 use thiserror::Error;
 use winit::{
-	event::{ElementState, Event, KeyboardInput, VirtualKeyCode, WindowEvent},
+	event::{Event as WinitEvent, WindowEvent},
 	event_loop::EventLoop,
 };
 
-use crate::widget::Widget;
+use crate::{
+	color::Color,
+	event::{ElementState, Event, Ime, Key, Modifiers, MouseButton, MouseScrollDelta},
+	text::Font,
+	view::{PhysicalPosition, PhysicalSize},
+	widget::Widget,
+};
 
 type Result<T> = std::result::Result<T, Error>;
 
@@ -31,20 +37,21 @@ pub enum Error {
 }
 
 mod inner {
-	use ab_glyph::FontRef;
 	use winit::{
-		event::WindowEvent,
 		event_loop::EventLoop,
 		window::{Window, WindowId},
 	};
 
 	use super::{Error, Result};
 	use crate::{
-		context::Context,
-		render::{Render, RenderContext, Vertex},
+		color::Color,
+		event::{self, Event, Pointer},
+		focus::Focus,
+		layout::Layout,
+		renderer::Renderer,
 		text::Font,
-		view::GlobalView,
-		widget::{Widget, WidgetContext},
+		view::PhysicalSize,
+		widget::Widget,
 	};
 
 	pub struct WindowInner<T> {
@@ -53,18 +60,19 @@ mod inner {
 		queue: wgpu::Queue,
 		config: wgpu::SurfaceConfiguration,
 		surface: wgpu::Surface,
-		pipeline: wgpu::RenderPipeline,
-		global_view: GlobalView,
-		font: Font,
-		size: winit::dpi::PhysicalSize<u32>,
+		renderer: Renderer,
+		size: PhysicalSize<u32>,
 		widget: T,
-		bind_group_layout: wgpu::BindGroupLayout,
-		sampler: wgpu::Sampler,
+		layout: Option<Layout>,
+		pointer: Pointer,
+		focus: Focus,
 	}
 
 	impl<T: Widget> WindowInner<T> {
 		pub async fn new(event_loop: &EventLoop<()>, widget: T) -> Result<Self> {
 			let window = Window::new(event_loop)?;
+			// Lets text inputs receive text composed with an input method.
+			window.set_ime_allowed(true);
 
 			let instance = wgpu::Instance::new(wgpu::InstanceDescriptor {
 				backends: wgpu::Backends::all(),
@@ -82,7 +90,7 @@ mod inner {
 				.await
 				.ok_or(Error::AdapterNotFound)?;
 
-			let size = window.inner_size();
+			let size = super::size(window.inner_size());
 
 			let surface_caps = surface.get_capabilities(&adapter);
 			let surface_format = surface_caps
@@ -116,88 +124,8 @@ mod inner {
 
 			surface.configure(&device, &config);
 
-			let bind_group_layout =
-				device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
-					label: None,
-					entries: &[
-						wgpu::BindGroupLayoutEntry {
-							binding: 0,
-							visibility: wgpu::ShaderStages::FRAGMENT,
-							ty: wgpu::BindingType::Texture {
-								sample_type: wgpu::TextureSampleType::Float { filterable: true },
-								view_dimension: wgpu::TextureViewDimension::D2,
-								multisampled: false,
-							},
-							count: None,
-						},
-						wgpu::BindGroupLayoutEntry {
-							binding: 1,
-							visibility: wgpu::ShaderStages::FRAGMENT,
-							ty: wgpu::BindingType::Sampler(wgpu::SamplerBindingType::Filtering),
-							count: None,
-						},
-					],
-				});
-
-			let sampler = device.create_sampler(&wgpu::SamplerDescriptor {
-				label: None,
-				address_mode_u: wgpu::AddressMode::ClampToEdge,
-				address_mode_v: wgpu::AddressMode::ClampToEdge,
-				address_mode_w: wgpu::AddressMode::ClampToEdge,
-				mag_filter: wgpu::FilterMode::Linear,
-				min_filter: wgpu::FilterMode::Linear,
-				mipmap_filter: wgpu::FilterMode::Nearest,
-				..Default::default()
-			});
-
-			let font = Font::new(FontRef::try_from_slice(include_bytes!(
-				"../res/Roboto/Roboto-Medium.ttf"
-			))?);
-
-			let shader = device.create_shader_module(wgpu::include_wgsl!("shader.wgsl"));
-
-			let layout = device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
-				label: Some("Render Pipeline Layout"),
-				bind_group_layouts: &[&bind_group_layout],
-				push_constant_ranges: &[],
-			});
-
-			let pipeline = device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
-				label: Some("Render Pipeline"),
-				layout: Some(&layout),
-				vertex: wgpu::VertexState {
-					module: &shader,
-					entry_point: "vs_main",
-					buffers: &[Vertex::layout()],
-				},
-				fragment: Some(wgpu::FragmentState {
-					module: &shader,
-					entry_point: "fs_main",
-					targets: &[Some(wgpu::ColorTargetState {
-						format: config.format,
-						blend: Some(wgpu::BlendState::ALPHA_BLENDING),
-						write_mask: wgpu::ColorWrites::ALL,
-					})],
-				}),
-				primitive: wgpu::PrimitiveState {
-					topology: wgpu::PrimitiveTopology::TriangleList,
-					strip_index_format: None,
-					front_face: wgpu::FrontFace::Ccw,
-					cull_mode: Some(wgpu::Face::Back),
-					polygon_mode: wgpu::PolygonMode::Fill,
-					unclipped_depth: false,
-					conservative: false,
-				},
-				depth_stencil: None,
-				multisample: wgpu::MultisampleState {
-					count: 1,
-					mask: !0,
-					alpha_to_coverage_enabled: false,
-				},
-				multiview: None,
-			});
-
-			let global_view = GlobalView::new(size);
+			let mut renderer = Renderer::new(&device, &queue, config.format)?;
+			renderer.set_scale_factor(window.scale_factor() as f32);
 
 			Ok(Self {
 				window,
@@ -206,12 +134,11 @@ mod inner {
 				queue,
 				config,
 				surface,
-				font,
-				pipeline,
-				global_view,
+				renderer,
 				widget,
-				sampler,
-				bind_group_layout,
+				layout: None,
+				pointer: Pointer::new(),
+				focus: Focus::new(),
 			})
 		}
 
@@ -223,7 +150,24 @@ mod inner {
 			self.window.request_redraw()
 		}
 
-		pub fn resize(&mut self, inner_size: winit::dpi::PhysicalSize<u32>) {
+		pub fn set_background(&mut self, color: Color) {
+			self.renderer.set_background(color);
+		}
+
+		pub fn set_font(&mut self, font: Font) {
+			self.renderer.set_font(font);
+		}
+
+		pub fn set_font_size(&mut self, size: f32) {
+			self.renderer.set_font_size(size);
+		}
+
+		pub fn set_scale_factor(&mut self, factor: f64) {
+			self.renderer
+				.set_scale_factor(factor as f32);
+		}
+
+		pub fn resize(&mut self, inner_size: PhysicalSize<u32>) {
 			self.size = inner_size;
 			self.config.width = inner_size.width;
 			self.config.height = inner_size.height;
@@ -231,8 +175,6 @@ mod inner {
 			self.surface
 				.configure(&self.device, &self.config);
 
-			self.global_view = GlobalView::new(inner_size);
-
 			self.widget.resize(inner_size);
 		}
 
@@ -244,63 +186,44 @@ mod inner {
 				.texture
 				.create_view(&Default::default());
 
-			let mut encoder = self
-				.device
-				.create_command_encoder(&Default::default());
-
-			let view = self
-				.global_view
-				.view(self.size, winit::dpi::PhysicalPosition::new(0, 0));
-
-			let mut context = Context::new(WidgetContext::new(
-				&self.font,
+			let layout = self.renderer.draw(
 				&self.device,
 				&self.queue,
-				&self.config,
-				&self.sampler,
-				&self.bind_group_layout,
-			));
-
-			let widget = self
-				.widget
-				.get_renderable(&mut context, view);
-
-			{
-				let mut pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
-					label: Some("Render Pass"),
-					color_attachments: &[Some(wgpu::RenderPassColorAttachment {
-						view: &texture_view,
-						resolve_target: None,
-						ops: wgpu::Operations {
-							load: wgpu::LoadOp::Clear(wgpu::Color {
-								r: 0.1,
-								g: 0.5,
-								b: 0.9,
-								a: 1.0,
-							}),
-							store: true,
-						},
-					})],
-					depth_stencil_attachment: None,
-				});
-
-				pass.set_pipeline(&self.pipeline);
-
-				let mut context = Context::new(RenderContext::new(pass));
-
-				widget.render(&mut context);
-			}
-
-			self.queue
-				.submit(Some(encoder.finish()));
+				&texture_view,
+				self.size,
+				&mut self.widget,
+			);
+			self.focus
+				.update(&mut self.widget, &layout);
+			self.layout = Some(layout);
 
 			output.present();
 
 			Ok(())
 		}
 
-		pub fn handle(&mut self, event: &WindowEvent) {
-			self.widget.handle(event);
+		pub fn handle(&mut self, event: &Event) {
+			let is_pointer_event = Pointer::is_pointer_event(event);
+			if !is_pointer_event && !Focus::is_key_event(event) {
+				self.widget.handle(event);
+				return;
+			}
+
+			// Pointer and keyboard events can only be routed once the widget has been laid out.
+			let Some(layout) = &self.layout else {
+				return;
+			};
+
+			if !is_pointer_event {
+				self.focus
+					.handle_key(&mut self.widget, layout, event);
+			} else if let Some(event) = self.pointer.event(event) {
+				// Focus moves first, so a widget that is clicked already has focus when it
+				// handles the click.
+				self.focus
+					.handle_pointer(&mut self.widget, layout, &event);
+				event::dispatch(&mut self.widget, layout, &event);
+			}
 		}
 	}
 }
@@ -318,42 +241,131 @@ impl<T: Widget> Window<T> {
 		Ok(Self { event_loop, inner })
 	}
 
+	/// Draw `color` behind the widget instead of the default blue.
+	pub fn with_background(mut self, color: Color) -> Self {
+		self.inner.set_background(color);
+		self
+	}
+
+	pub fn with_font(mut self, font: Font) -> Self {
+		self.inner.set_font(font);
+		self
+	}
+
+	pub fn with_font_size(mut self, size: f32) -> Self {
+		self.inner.set_font_size(size);
+		self
+	}
+
 	pub fn run(mut self) -> !
 	where
 		T: 'static,
 	{
 		self.event_loop
-			.run(move |event, _, control_flow| match event {
-				Event::WindowEvent { window_id, event } if self.inner.id() == window_id => {
-					match event {
-						WindowEvent::CloseRequested
-						| WindowEvent::KeyboardInput {
-							input:
-								KeyboardInput {
-									state: ElementState::Pressed,
-									virtual_keycode: Some(VirtualKeyCode::Escape),
-									..
-								},
-							..
-						} => control_flow.set_exit(),
-						WindowEvent::Resized(new_size)
-						| WindowEvent::ScaleFactorChanged {
-							new_inner_size: &mut new_size,
-							..
-						} => self.inner.resize(new_size),
-						event => {
-							self.inner.handle(&event);
+			.run(move |event, _, control_flow| {
+				let event = match event {
+					WinitEvent::WindowEvent { window_id, event }
+						if self.inner.id() == window_id =>
+					{
+						match event {
+							WindowEvent::ScaleFactorChanged {
+								scale_factor,
+								new_inner_size,
+							} => {
+								self.inner
+									.set_scale_factor(scale_factor);
+								Event::Resized(size(*new_inner_size))
+							}
+							event => match translate(&event) {
+								Some(event) => event,
+								None => return,
+							},
 						}
 					}
-				}
-				Event::MainEventsCleared => self.inner.request_redraw(),
-				Event::RedrawRequested(window_id) if self.inner.id() == window_id => {
-					let result = self.inner.draw();
-					if result.is_err() {
-						control_flow.set_exit();
+					WinitEvent::Suspended => Event::Suspended,
+					WinitEvent::Resumed => Event::Resumed,
+					WinitEvent::MainEventsCleared => {
+						self.inner.request_redraw();
+						return;
 					}
+					WinitEvent::RedrawRequested(window_id) if self.inner.id() == window_id => {
+						let result = self.inner.draw();
+						if result.is_err() {
+							control_flow.set_exit();
+						}
+						return;
+					}
+					_ => return,
+				};
+
+				match event {
+					Event::CloseRequested
+					| Event::Keyboard {
+						key: Key::Escape,
+						state: ElementState::Pressed,
+					} => control_flow.set_exit(),
+					Event::Resized(new_size) => {
+						self.inner.resize(new_size);
+						self.inner.handle(&event);
+					}
+					event => self.inner.handle(&event),
 				}
-				_ => {}
 			});
 	}
 }
+
+fn size(size: winit::dpi::PhysicalSize<u32>) -> PhysicalSize<u32> {
+	PhysicalSize::new(size.width, size.height)
+}
+
+fn state(state: winit::event::ElementState) -> ElementState {
+	match state {
+		winit::event::ElementState::Pressed => ElementState::Pressed,
+		winit::event::ElementState::Released => ElementState::Released,
+	}
+}
+
+fn translate(event: &WindowEvent) -> Option<Event> {
+	Some(match event {
+		WindowEvent::CursorMoved { position, .. } => {
+			Event::CursorMoved(PhysicalPosition::new(position.x, position.y))
+		}
+		WindowEvent::CursorLeft { .. } => Event::CursorLeft,
+		WindowEvent::MouseInput { state, button, .. } => Event::MouseInput {
+			button: match *button {
+				winit::event::MouseButton::Left => MouseButton::Left,
+				winit::event::MouseButton::Right => MouseButton::Right,
+				winit::event::MouseButton::Middle => MouseButton::Middle,
+				winit::event::MouseButton::Other(x) => MouseButton::Other(x),
+			},
+			state: self::state(*state),
+		},
+		WindowEvent::MouseWheel { delta, .. } => Event::MouseWheel(match *delta {
+			winit::event::MouseScrollDelta::LineDelta(x, y) => MouseScrollDelta::LineDelta(x, y),
+			winit::event::MouseScrollDelta::PixelDelta(position) => {
+				MouseScrollDelta::PixelDelta(PhysicalPosition::new(position.x, position.y))
+			}
+		}),
+		WindowEvent::KeyboardInput { input, .. } => Event::Keyboard {
+			key: Key::from_winit(input.virtual_keycode, input.scancode),
+			state: state(input.state),
+		},
+		WindowEvent::ReceivedCharacter(character) => Event::Text(*character),
+		WindowEvent::Ime(ime) => Event::Ime(match ime {
+			winit::event::Ime::Enabled => Ime::Enabled,
+			winit::event::Ime::Preedit(text, cursor) => Ime::Preedit(text.clone(), *cursor),
+			winit::event::Ime::Commit(text) => Ime::Commit(text.clone()),
+			winit::event::Ime::Disabled => Ime::Disabled,
+		}),
+		WindowEvent::ModifiersChanged(modifiers) => Event::ModifiersChanged(Modifiers {
+			shift: modifiers.shift(),
+			ctrl: modifiers.ctrl(),
+			alt: modifiers.alt(),
+			logo: modifiers.logo(),
+		}),
+		WindowEvent::Focused(focused) => Event::Focused(*focused),
+		WindowEvent::Resized(new_size) => Event::Resized(size(*new_size)),
+		WindowEvent::CloseRequested => Event::CloseRequested,
+		_ => return None,
+	})
+}
@@ -0,0 +1,88 @@
+use kitsune_ui::{
+	atlas::Packer,
+	view::{PhysicalPosition, PhysicalSize},
+};
+
+#[test]
+fn rectangles_are_packed_in_rows() {
+	let mut packer = Packer::new(100);
+	let size = PhysicalSize::new(40, 30);
+
+	assert_eq!(packer.insert('a', size), Some(PhysicalPosition::new(0, 0)));
+	assert_eq!(packer.insert('b', size), Some(PhysicalPosition::new(40, 0)));
+	assert_eq!(packer.insert('c', size), Some(PhysicalPosition::new(0, 30)));
+	assert_eq!(
+		packer.insert('d', PhysicalSize::new(20, 10)),
+		Some(PhysicalPosition::new(80, 0))
+	);
+	assert_eq!(packer.get(&'c'), Some((PhysicalPosition::new(0, 30), size)));
+}
+
+#[test]
+fn the_least_recently_used_rectangle_is_evicted() {
+	let mut packer = Packer::new(100);
+	let half = PhysicalSize::new(100, 50);
+	packer.insert('a', half);
+	packer.insert('b', half);
+
+	packer.next_frame();
+	packer.get(&'a');
+	packer.next_frame();
+	packer.get(&'b');
+	packer.next_frame();
+
+	assert_eq!(packer.insert('c', half), Some(PhysicalPosition::new(0, 0)));
+	assert!(!packer.contains(&'a'));
+	assert!(packer.contains(&'b'));
+	assert_eq!(packer.evictions(), 1);
+}
+
+#[test]
+fn rectangles_used_in_this_frame_are_not_evicted() {
+	let mut packer = Packer::new(100);
+	let half = PhysicalSize::new(100, 50);
+	packer.insert('a', half);
+	packer.insert('b', half);
+
+	assert_eq!(packer.insert('c', half), None);
+	assert_eq!(packer.insert('d', PhysicalSize::new(101, 1)), None);
+	assert_eq!(packer.evictions(), 0);
+}
+
+#[test]
+fn evicted_space_is_merged_for_larger_rectangles() {
+	let mut packer = Packer::new(100);
+	packer.insert('a', PhysicalSize::new(50, 100));
+	packer.insert('b', PhysicalSize::new(50, 100));
+	packer.next_frame();
+
+	assert_eq!(
+		packer.insert('c', PhysicalSize::new(100, 100)),
+		Some(PhysicalPosition::new(0, 0))
+	);
+	assert_eq!(packer.evictions(), 2);
+}
+
+#[test]
+fn growing_keeps_rectangles_and_adds_room_next_to_and_below_them() {
+	let mut packer = Packer::new(100);
+	let size = PhysicalSize::new(60, 50);
+	packer.insert('a', size);
+	packer.insert('b', size);
+	assert_eq!(packer.insert('c', size), None);
+
+	packer.grow(200);
+
+	assert_eq!(packer.size(), 200);
+	assert_eq!(packer.get(&'a'), Some((PhysicalPosition::new(0, 0), size)));
+	assert_eq!(packer.insert('c', size), Some(PhysicalPosition::new(60, 0)));
+	assert_eq!(
+		packer.insert('d', size),
+		Some(PhysicalPosition::new(120, 0))
+	);
+	assert_eq!(
+		packer.insert('e', PhysicalSize::new(200, 100)),
+		Some(PhysicalPosition::new(0, 100))
+	);
+	assert_eq!(packer.evictions(), 0);
+}
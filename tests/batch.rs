@@ -0,0 +1,174 @@
+use std::rc::Rc;
+
+use kitsune_ui::{
+	color::Color,
+	context::Context,
+	fill::Fill,
+	render::{Batch, Clipped, Render, RenderContext, RenderedMesh, RenderedShape},
+	shape::Shape,
+	view::{GlobalView, PhysicalPosition, PhysicalSize, View},
+};
+
+const SIZE: PhysicalSize<u32> = PhysicalSize::new(100, 100);
+
+async fn device() -> wgpu::Device {
+	let instance = wgpu::Instance::new(wgpu::InstanceDescriptor::default());
+	let adapter = match instance
+		.request_adapter(&Default::default())
+		.await
+	{
+		Some(adapter) => adapter,
+		None => instance
+			.request_adapter(&wgpu::RequestAdapterOptions {
+				force_fallback_adapter: true,
+				..Default::default()
+			})
+			.await
+			.expect("could not find an adapter"),
+	};
+
+	let (device, _) = adapter
+		.request_device(
+			&wgpu::DeviceDescriptor {
+				limits: wgpu::Limits::downlevel_defaults().using_resolution(adapter.limits()),
+				..Default::default()
+			},
+			None,
+		)
+		.await
+		.expect("could not request a device");
+	device
+}
+
+/// A bind group without any bindings, which stands in for a texture.
+fn texture(device: &wgpu::Device) -> Rc<wgpu::BindGroup> {
+	let layout = device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+		label: None,
+		entries: &[],
+	});
+	Rc::new(device.create_bind_group(&wgpu::BindGroupDescriptor {
+		label: None,
+		layout: &layout,
+		entries: &[],
+	}))
+}
+
+fn view(x: u32, y: u32, width: u32, height: u32) -> View {
+	GlobalView::new(SIZE).view(
+		PhysicalSize::new(width, height),
+		PhysicalPosition::new(x, y),
+	)
+}
+
+/// A quad covering `view`, drawn with `texture`.
+fn mesh(view: &View, texture: &Rc<wgpu::BindGroup>) -> RenderedMesh {
+	let (vertices, indices) = Fill::Solid(Color::WHITE).mesh(view);
+	RenderedMesh::new(vertices, indices, texture.clone())
+}
+
+fn shape(view: &View) -> RenderedShape {
+	let (vertices, indices) = Shape::new(Color::WHITE).mesh(view);
+	RenderedShape::new(vertices, indices)
+}
+
+fn batch<'a>(blank: &'a wgpu::BindGroup, renderable: &'a impl Render) -> Batch<'a> {
+	let mut context = Context::new(RenderContext::new(SIZE, blank));
+	renderable.render(&mut context);
+	context.into_inner().into_batch()
+}
+
+#[tokio::test]
+async fn renderables_with_the_same_texture_and_clip_are_drawn_together() {
+	let device = device().await;
+	let (blank, atlas) = (texture(&device), texture(&device));
+	let renderable = (
+		mesh(&view(0, 0, 10, 10), &atlas),
+		mesh(&view(20, 0, 10, 10), &atlas),
+		Clipped::new(
+			&view(0, 50, 100, 50),
+			vec![
+				mesh(&view(0, 50, 10, 10), &atlas),
+				mesh(&view(20, 50, 10, 10), &atlas),
+			],
+		),
+	);
+
+	let batch = batch(&blank, &renderable);
+
+	assert_eq!(batch.draws().collect::<Vec<_>>(), vec![0..12, 12..24]);
+}
+
+#[tokio::test]
+async fn changing_the_texture_clip_or_kind_starts_a_new_draw() {
+	let device = device().await;
+	let (blank, atlas, image) = (texture(&device), texture(&device), texture(&device));
+	let renderable = (
+		mesh(&view(0, 0, 10, 10), &atlas),
+		mesh(&view(10, 0, 10, 10), &image),
+		Clipped::new(&view(0, 0, 50, 50), mesh(&view(20, 0, 10, 10), &image)),
+		shape(&view(30, 0, 10, 10)),
+		mesh(&view(40, 0, 10, 10), &image),
+		{
+			let (vertices, indices) = Fill::Solid(Color::BLACK).mesh(&view(50, 0, 10, 10));
+			RenderedMesh::untextured(vertices, indices)
+		},
+	);
+
+	let batch = batch(&blank, &renderable);
+
+	assert_eq!(
+		batch.draws().collect::<Vec<_>>(),
+		vec![0..6, 6..12, 12..18, 18..24, 24..30, 30..36]
+	);
+}
+
+#[tokio::test]
+async fn indices_point_into_the_vertices_of_their_own_kind() {
+	let device = device().await;
+	let blank = texture(&device);
+	let quad = |x: u32| {
+		(0..4)
+			.map(|i| i + x)
+			.collect::<Vec<_>>()
+	};
+	let renderable = (
+		mesh(&view(0, 0, 10, 10), &blank),
+		shape(&view(10, 0, 10, 10)),
+		mesh(&view(20, 0, 10, 10), &blank),
+		shape(&view(30, 0, 10, 10)),
+	);
+
+	let batch = batch(&blank, &renderable);
+
+	let indices = batch
+		.indices()
+		.chunks(6)
+		.map(|x| {
+			let mut x = x.to_vec();
+			x.sort();
+			x.dedup();
+			x
+		})
+		.collect::<Vec<_>>();
+	assert_eq!(indices, vec![quad(0), quad(0), quad(4), quad(4)]);
+}
+
+#[tokio::test]
+async fn meshes_can_have_more_vertices_than_fit_in_16_bits() {
+	let device = device().await;
+	let blank = texture(&device);
+	let (vertices, _) = Fill::Solid(Color::WHITE).mesh(&view(0, 0, 10, 10));
+	let vertices = vertices
+		.into_iter()
+		.cycle()
+		.take(70_000)
+		.collect::<Vec<_>>();
+	let renderable = (
+		mesh(&view(0, 0, 10, 10), &blank),
+		RenderedMesh::new(vertices, vec![69_999], blank.clone()),
+	);
+
+	let batch = batch(&blank, &renderable);
+
+	assert_eq!(batch.indices().last(), Some(&70_003));
+}
@@ -0,0 +1,123 @@
+mod common;
+
+use std::{cell::Cell, rc::Rc};
+
+use common::{layout, Square};
+use kitsune_ui::{
+	event::{dispatch, ElementState, Event, Key, MouseButton, PointerEvent, PointerKind},
+	focus::Focus,
+	view::PhysicalPosition,
+	widget::{Button, Row, Widget},
+};
+
+/// A 50 by 50 button that counts how often it is clicked.
+fn button(clicks: &Rc<Cell<u32>>) -> Button<Square, impl FnMut()> {
+	let clicks = clicks.clone();
+	Square(50, 50).on_click(move || clicks.set(clicks.get() + 1))
+}
+
+fn mouse(state: ElementState, x: f64, y: f64) -> PointerEvent {
+	let kind = PointerKind::Button {
+		button: MouseButton::Left,
+		state,
+	};
+	PointerEvent::new(kind, PhysicalPosition::new(x, y), None)
+}
+
+fn moved(from: (f64, f64), to: (f64, f64)) -> PointerEvent {
+	PointerEvent::new(
+		PointerKind::Moved,
+		PhysicalPosition::new(to.0, to.1),
+		Some(PhysicalPosition::new(from.0, from.1)),
+	)
+}
+
+#[test]
+fn releasing_the_mouse_over_the_button_clicks_it() {
+	let clicks = Rc::new(Cell::new(0));
+	let mut widget = button(&clicks);
+	let layout = layout(&widget, 200, 100);
+
+	dispatch(
+		&mut widget,
+		&layout,
+		&mouse(ElementState::Pressed, 20.0, 20.0),
+	);
+	assert_eq!(clicks.get(), 0);
+	dispatch(
+		&mut widget,
+		&layout,
+		&mouse(ElementState::Released, 30.0, 20.0),
+	);
+	assert_eq!(clicks.get(), 1);
+
+	// Releasing without pressing first does nothing.
+	dispatch(
+		&mut widget,
+		&layout,
+		&mouse(ElementState::Released, 30.0, 20.0),
+	);
+	assert_eq!(clicks.get(), 1);
+}
+
+#[test]
+fn leaving_the_button_while_pressed_cancels_the_click() {
+	let clicks = Rc::new(Cell::new(0));
+	let mut widget = Row::new(vec![button(&clicks), button(&clicks)]).spaced(10);
+	let layout = layout(&widget, 200, 100);
+
+	dispatch(
+		&mut widget,
+		&layout,
+		&mouse(ElementState::Pressed, 20.0, 20.0),
+	);
+	assert!(widget[0].pressed());
+
+	dispatch(&mut widget, &layout, &moved((20.0, 20.0), (80.0, 20.0)));
+	assert!(!widget[0].pressed());
+	assert!(!widget[0].hovered());
+	assert!(widget[1].hovered());
+
+	dispatch(
+		&mut widget,
+		&layout,
+		&mouse(ElementState::Released, 80.0, 20.0),
+	);
+	assert_eq!(clicks.get(), 0);
+}
+
+#[test]
+fn enter_and_space_click_the_focused_button() {
+	let clicks = Rc::new(Cell::new(0));
+	let mut widget = button(&clicks);
+	let layout = layout(&widget, 200, 100);
+	let mut focus = Focus::new();
+
+	let key = |key, state| Event::Keyboard { key, state };
+
+	focus.handle_key(
+		&mut widget,
+		&layout,
+		&key(Key::Return, ElementState::Pressed),
+	);
+	assert_eq!(clicks.get(), 0);
+
+	focus.focus(&mut widget, &layout, Some(0));
+	focus.handle_key(
+		&mut widget,
+		&layout,
+		&key(Key::Return, ElementState::Pressed),
+	);
+	focus.handle_key(
+		&mut widget,
+		&layout,
+		&key(Key::Space, ElementState::Pressed),
+	);
+	focus.handle_key(
+		&mut widget,
+		&layout,
+		&key(Key::Space, ElementState::Released),
+	);
+	focus.handle_key(&mut widget, &layout, &key(Key::A, ElementState::Pressed));
+	assert_eq!(clicks.get(), 2);
+}
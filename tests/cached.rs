@@ -0,0 +1,130 @@
+mod common;
+
+use std::{cell::Cell, rc::Rc};
+
+use common::layout;
+use kitsune_ui::{
+	context::Context,
+	event::{dispatch, PointerEvent, PointerKind},
+	headless::{self, HeadlessRenderer},
+	layout::{Layout, LayoutContext},
+	render::RenderedMesh,
+	view::{PhysicalPosition, PhysicalSize, SizeHint, View},
+	widget::{Button, Widget, WidgetContext},
+};
+
+/// A leaf with a fixed size that counts how often it is rendered.
+struct Counted(Rc<Cell<u32>>);
+
+impl Widget for Counted {
+	type Renderable = Option<RenderedMesh>;
+
+	fn layout(&self, context: &Context<LayoutContext>, view: View) -> Layout {
+		let width = self.width_hint(context, &view);
+		let height = self.height_hint(context, &view);
+		Layout::leaf(view.from_size_hints(width, height))
+	}
+
+	fn get_renderable(&mut self, _: &mut Context<WidgetContext>, _: &Layout) -> Self::Renderable {
+		self.0.set(self.0.get() + 1);
+		None
+	}
+
+	fn width_hint(&self, _: &Context<LayoutContext>, _: &View) -> SizeHint {
+		SizeHint::Physical(50)
+	}
+
+	fn height_hint(&self, _: &Context<LayoutContext>, _: &View) -> SizeHint {
+		SizeHint::Physical(50)
+	}
+}
+
+const SIZE: PhysicalSize<u32> = PhysicalSize::new(100, 100);
+
+async fn renderer() -> HeadlessRenderer {
+	match HeadlessRenderer::new(true).await {
+		Err(headless::Error::AdapterNotFound) => HeadlessRenderer::new(false).await,
+		renderer => renderer,
+	}
+	.expect("could not create a headless renderer")
+}
+
+/// Move the pointer to `x` and `y` over `widget`.
+fn move_to<T: Widget>(widget: &mut T, x: f64, y: f64) {
+	let layout = layout(widget, SIZE.width, SIZE.height);
+	let event = PointerEvent::new(PointerKind::Moved, PhysicalPosition::new(x, y), None);
+	dispatch(widget, &layout, &event);
+}
+
+#[tokio::test]
+async fn moving_the_pointer_keeps_the_cache() {
+	let renderer = renderer().await;
+	let renders = Rc::new(Cell::new(0));
+	let mut widget = Counted(renders.clone()).cached();
+
+	renderer
+		.render(&mut widget, SIZE)
+		.unwrap();
+	move_to(&mut widget, 20.0, 20.0);
+	renderer
+		.render(&mut widget, SIZE)
+		.unwrap();
+
+	assert_eq!(renders.get(), 1);
+}
+
+#[tokio::test]
+async fn changes_reported_by_the_value_are_rendered() {
+	let renderer = renderer().await;
+	let renders = Rc::new(Cell::new(0));
+	let mut widget = Button::new(Counted(renders.clone()), || {}).cached();
+
+	renderer
+		.render(&mut widget, SIZE)
+		.unwrap();
+	move_to(&mut widget, 20.0, 20.0);
+	renderer
+		.render(&mut widget, SIZE)
+		.unwrap();
+	assert_eq!(renders.get(), 2);
+
+	// The button is still hovered, so it looks the same.
+	move_to(&mut widget, 30.0, 30.0);
+	renderer
+		.render(&mut widget, SIZE)
+		.unwrap();
+	assert_eq!(renders.get(), 2);
+}
+
+#[tokio::test]
+async fn invalidating_renders_the_value_again() {
+	let renderer = renderer().await;
+	let renders = Rc::new(Cell::new(0));
+	let mut widget = Counted(renders.clone()).cached();
+
+	renderer
+		.render(&mut widget, SIZE)
+		.unwrap();
+	widget.invalidate();
+	renderer
+		.render(&mut widget, SIZE)
+		.unwrap();
+
+	assert_eq!(renders.get(), 2);
+}
+
+#[tokio::test]
+async fn a_new_layout_renders_the_value_again() {
+	let renderer = renderer().await;
+	let renders = Rc::new(Cell::new(0));
+	let mut widget = Counted(renders.clone()).cached();
+
+	renderer
+		.render(&mut widget, SIZE)
+		.unwrap();
+	renderer
+		.render(&mut widget, PhysicalSize::new(200, 100))
+		.unwrap();
+
+	assert_eq!(renders.get(), 2);
+}
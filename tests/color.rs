@@ -0,0 +1,54 @@
+use kitsune_ui::color::{Color, ParseColorError};
+
+#[test]
+fn hex_codes_are_parsed_as_srgb() {
+	assert_eq!(Color::hex("#ff8000"), Ok(Color::rgb(255, 128, 0)));
+	assert_eq!(Color::hex("FF800080"), Ok(Color::rgba(255, 128, 0, 128)));
+	assert_eq!(Color::hex("#f80"), Ok(Color::rgb(255, 136, 0)));
+	assert_eq!("#f808".parse(), Ok(Color::rgba(255, 136, 0, 136)));
+}
+
+#[test]
+fn invalid_hex_codes_are_rejected() {
+	assert_eq!(
+		Color::hex("#gg0000"),
+		Err(ParseColorError::InvalidDigit(String::from("#gg0000")))
+	);
+	assert_eq!(
+		Color::hex("#ff800"),
+		Err(ParseColorError::InvalidLength(String::from("#ff800"), 5))
+	);
+	assert_eq!(
+		Color::hex("#+f8000"),
+		Err(ParseColorError::InvalidDigit(String::from("#+f8000")))
+	);
+	assert_eq!(
+		Color::hex("#ff80é"),
+		Err(ParseColorError::InvalidDigit(String::from("#ff80é")))
+	);
+}
+
+#[test]
+fn colors_are_stored_in_linear_space() {
+	let color = Color::rgba(255, 128, 0, 51);
+	let [red, green, blue, alpha] = color.to_linear();
+
+	assert_eq!((red, blue), (1.0, 0.0));
+	assert!((green - 0.2158).abs() < 1e-3);
+	assert!((alpha - 0.2).abs() < 1e-6);
+	assert_eq!(color.to_srgb(), [255, 128, 0, 51]);
+}
+
+#[test]
+fn colors_are_mixed_in_linear_space() {
+	let gray = Color::BLACK.mix(Color::WHITE, 0.5);
+
+	assert_eq!(gray.to_linear(), [0.5, 0.5, 0.5, 1.0]);
+	assert_eq!(gray.to_srgb(), [188, 188, 188, 255]);
+	assert_eq!(
+		Color::WHITE
+			.with_alpha(0.25)
+			.alpha(),
+		0.25
+	);
+}
@@ -0,0 +1,61 @@
+//! Widgets and helpers shared by the integration tests, each of which only uses some of them.
+#![allow(dead_code)]
+
+use kitsune_ui::{
+	context::Context,
+	layout::{Layout, LayoutContext},
+	render::RenderedMesh,
+	text::Font,
+	view::{GlobalView, PhysicalPosition, PhysicalSize, SizeHint, View},
+	widget::{prepared_layout, Widget, WidgetContext},
+};
+
+/// A leaf with a fixed width and height.
+pub struct Square(pub u32, pub u32);
+
+impl Widget for Square {
+	type Renderable = Option<RenderedMesh>;
+
+	fn layout(&self, context: &Context<LayoutContext>, view: View) -> Layout {
+		let width = self.width_hint(context, &view);
+		let height = self.height_hint(context, &view);
+		Layout::leaf(view.from_size_hints(width, height))
+	}
+
+	fn get_renderable(&mut self, _: &mut Context<WidgetContext>, _: &Layout) -> Self::Renderable {
+		None
+	}
+
+	fn width_hint(&self, _: &Context<LayoutContext>, _: &View) -> SizeHint {
+		SizeHint::Physical(self.0)
+	}
+
+	fn height_hint(&self, _: &Context<LayoutContext>, _: &View) -> SizeHint {
+		SizeHint::Physical(self.1)
+	}
+}
+
+/// Lay out `widget` in a window that is `width` by `height` pixels.
+pub fn layout<T: Widget>(widget: &T, width: u32, height: u32) -> Layout {
+	let font = Font::roboto().unwrap();
+	let context = Context::new(LayoutContext::new(&font));
+
+	let size = PhysicalSize::new(width, height);
+	widget.layout(
+		&context,
+		GlobalView::new(size).view(size, PhysicalPosition::new(0, 0)),
+	)
+}
+
+/// Lay out `widget` like the renderer does, preparing it for the layout.
+pub fn prepared<T: Widget>(widget: &mut T, width: u32, height: u32) -> Layout {
+	let font = Font::roboto().unwrap();
+	let context = Context::new(LayoutContext::new(&font));
+
+	let size = PhysicalSize::new(width, height);
+	prepared_layout(
+		widget,
+		&context,
+		GlobalView::new(size).view(size, PhysicalPosition::new(0, 0)),
+	)
+}
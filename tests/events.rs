@@ -0,0 +1,242 @@
+mod common;
+
+use std::{cell::RefCell, rc::Rc};
+
+use common::{layout, Square};
+use kitsune_ui::{
+	context::Context,
+	event::{
+		dispatch, ElementState, MouseButton, MouseScrollDelta, PointerEvent, PointerKind,
+		Propagation,
+	},
+	layout::{Anchor, Layout, LayoutContext},
+	view::{PhysicalPosition, SizeHint, View},
+	widget::{Column, Layer, Row, ScrollAxis, Stack, Widget, WidgetContext},
+};
+
+type Log = Rc<RefCell<Vec<(&'static str, PointerKind, (f64, f64))>>>;
+
+/// Records the pointer events it receives after its child had a chance to handle them.
+struct Recorder<T> {
+	name: &'static str,
+	log: Log,
+	stop: bool,
+	value: T,
+}
+
+impl<T: Widget> Widget for Recorder<T> {
+	type Renderable = T::Renderable;
+
+	fn layout(&self, context: &Context<LayoutContext>, view: View) -> Layout {
+		let child = self
+			.value
+			.layout(context, view.clone());
+		Layout::new(view, vec![child])
+	}
+
+	fn get_renderable(
+		&mut self,
+		context: &mut Context<WidgetContext>,
+		layout: &Layout,
+	) -> Self::Renderable {
+		self.value
+			.get_renderable(context, &layout.children()[0])
+	}
+
+	fn width_hint(&self, context: &Context<LayoutContext>, view: &View) -> SizeHint {
+		self.value
+			.width_hint(context, view)
+	}
+
+	fn height_hint(&self, context: &Context<LayoutContext>, view: &View) -> SizeHint {
+		self.value
+			.height_hint(context, view)
+	}
+
+	fn handle_pointer(&mut self, event: &PointerEvent, layout: &Layout) -> Propagation {
+		let propagation = self
+			.value
+			.handle_pointer(event, &layout.children()[0]);
+		if propagation == Propagation::Stop {
+			return propagation;
+		}
+
+		let position = event.position();
+		self.log
+			.borrow_mut()
+			.push((self.name, event.kind(), (position.x, position.y)));
+
+		if self.stop {
+			Propagation::Stop
+		} else {
+			Propagation::Continue
+		}
+	}
+}
+
+fn recorder<T>(name: &'static str, log: &Log, value: T) -> Recorder<T> {
+	Recorder {
+		name,
+		log: log.clone(),
+		stop: false,
+		value,
+	}
+}
+
+fn press(x: f64, y: f64) -> PointerEvent {
+	let kind = PointerKind::Button {
+		button: MouseButton::Left,
+		state: ElementState::Pressed,
+	};
+	PointerEvent::new(kind, PhysicalPosition::new(x, y), None)
+}
+
+fn moved(from: (f64, f64), to: (f64, f64)) -> PointerEvent {
+	PointerEvent::new(
+		PointerKind::Moved,
+		PhysicalPosition::new(to.0, to.1),
+		Some(PhysicalPosition::new(from.0, from.1)),
+	)
+}
+
+fn names(log: &Log) -> Vec<&'static str> {
+	log.borrow()
+		.iter()
+		.map(|(name, _, _)| *name)
+		.collect()
+}
+
+/// A row of two 50 by 50 squares, `a` and `b`, inside of `row`.
+fn pair(log: &Log) -> Recorder<Row<Recorder<Square>>> {
+	recorder(
+		"row",
+		log,
+		Row::new(vec![
+			recorder("a", log, Square(50, 50)),
+			recorder("b", log, Square(50, 50)),
+		])
+		.spaced(10),
+	)
+}
+
+#[test]
+fn events_only_reach_the_widgets_under_the_cursor() {
+	let log = Log::default();
+	let mut widget = pair(&log);
+	let layout = layout(&widget, 200, 100);
+
+	dispatch(&mut widget, &layout, &press(70.0, 20.0));
+	assert_eq!(names(&log), vec!["b", "row"]);
+
+	log.borrow_mut().clear();
+	dispatch(&mut widget, &layout, &press(55.0, 20.0));
+	assert_eq!(names(&log), vec!["row"]);
+
+	log.borrow_mut().clear();
+	dispatch(&mut widget, &layout, &press(20.0, 70.0));
+	assert_eq!(names(&log), vec!["row"]);
+}
+
+#[test]
+fn events_use_the_coordinates_of_the_widget() {
+	let log = Log::default();
+	let mut widget = pair(&log).padded(5, 0, 0, 5);
+	let layout = layout(&widget, 200, 100);
+
+	dispatch(&mut widget, &layout, &press(70.0, 20.0));
+
+	let positions = log
+		.borrow()
+		.iter()
+		.map(|(_, _, position)| *position)
+		.collect::<Vec<_>>();
+	assert_eq!(positions, vec![(5.0, 15.0), (65.0, 15.0)]);
+}
+
+#[test]
+fn events_stop_propagating_when_handled() {
+	let log = Log::default();
+	let mut widget = pair(&log);
+	widget.value[0].stop = true;
+	let layout = layout(&widget, 200, 100);
+
+	let propagation = dispatch(&mut widget, &layout, &press(20.0, 20.0));
+
+	assert_eq!(propagation, Propagation::Stop);
+	assert_eq!(names(&log), vec!["a"]);
+}
+
+#[test]
+fn widgets_are_told_when_the_cursor_leaves_them() {
+	let log = Log::default();
+	let mut widget = pair(&log);
+	let layout = layout(&widget, 200, 100);
+
+	dispatch(&mut widget, &layout, &moved((20.0, 20.0), (70.0, 25.0)));
+
+	assert_eq!(
+		*log.borrow(),
+		vec![
+			("a", PointerKind::Exited, (20.0, 20.0)),
+			("b", PointerKind::Moved, (10.0, 25.0)),
+			("row", PointerKind::Moved, (70.0, 25.0)),
+		]
+	);
+}
+
+#[test]
+fn only_the_top_layer_of_a_stack_receives_events() {
+	let log = Log::default();
+	let mut widget = Stack::new(vec![
+		Layer::new(recorder("top", &log, Square(50, 50)), Anchor::TopLeft).layered(1),
+		Layer::new(recorder("bottom", &log, Square(50, 50)), Anchor::TopLeft),
+	]);
+	let layout = layout(&widget, 200, 100);
+
+	dispatch(&mut widget, &layout, &press(20.0, 20.0));
+
+	assert_eq!(names(&log), vec!["top"]);
+}
+
+#[test]
+fn wrappers_and_tuples_forward_events() {
+	let log = Log::default();
+	let mut widget = (
+		recorder("below", &log, Square(100, 80)).padded(10, 0, 0, 10),
+		recorder("above", &log, Square(50, 50))
+			.bordered(5)
+			.cached(),
+	);
+	let layout = layout(&widget, 200, 100);
+
+	dispatch(&mut widget, &layout, &press(80.0, 50.0));
+	dispatch(&mut widget, &layout, &press(30.0, 30.0));
+
+	assert_eq!(names(&log), vec!["below", "above"]);
+}
+
+fn wheel(lines: f32) -> PointerEvent {
+	let kind = PointerKind::Wheel(MouseScrollDelta::LineDelta(0.0, lines));
+	PointerEvent::new(kind, PhysicalPosition::new(20.0, 20.0), None)
+}
+
+#[test]
+fn scrolling_stops_at_the_end_of_the_layout() {
+	let mut widget = Column::new(
+		(0..5)
+			.map(|_| Square(50, 50))
+			.collect(),
+	)
+	.scrollable(ScrollAxis::Vertical);
+	let layout = layout(&widget, 200, 100);
+
+	dispatch(&mut widget, &layout, &wheel(-1.0));
+	assert_eq!(widget.offset(), PhysicalPosition::new(0.0, 40.0));
+
+	dispatch(&mut widget, &layout, &wheel(-100.0));
+	assert_eq!(widget.offset(), PhysicalPosition::new(0.0, 150.0));
+
+	widget.scroll_to(PhysicalPosition::new(0.0, 1000.0));
+	dispatch(&mut widget, &layout, &wheel(1.0));
+	assert_eq!(widget.offset(), PhysicalPosition::new(0.0, 110.0));
+}
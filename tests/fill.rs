@@ -0,0 +1,49 @@
+use kitsune_ui::{color::Color, fill::Fill};
+
+fn assert_close(actual: [f32; 4], expected: [f32; 4]) {
+	for (a, b) in actual.into_iter().zip(expected) {
+		assert!((a - b).abs() < 1e-3, "{actual:?} is not {expected:?}");
+	}
+}
+
+#[test]
+fn solid_colors_are_converted_to_linear_space() {
+	let fill = Fill::Solid(Color::rgba(255, 128, 0, 51));
+
+	assert_close(fill.color_at(0.3, 0.7), [1.0, 0.2158, 0.0, 0.2]);
+}
+
+#[test]
+fn linear_gradients_blend_between_their_stops() {
+	let fill = Fill::Linear {
+		start: (0.0, 0.0),
+		end: (1.0, 0.0),
+		stops: vec![
+			(0.0, Color::rgb(0, 0, 0)),
+			(0.5, Color::rgb(255, 255, 255)),
+			(1.0, Color::rgb(255, 0, 0)),
+		],
+	};
+
+	assert_close(fill.color_at(-1.0, 0.0), [0.0, 0.0, 0.0, 1.0]);
+	assert_close(fill.color_at(0.25, 0.9), [0.5, 0.5, 0.5, 1.0]);
+	assert_close(fill.color_at(0.75, 0.0), [1.0, 0.5, 0.5, 1.0]);
+	assert_close(fill.color_at(2.0, 0.0), [1.0, 0.0, 0.0, 1.0]);
+}
+
+#[test]
+fn radial_gradients_depend_on_the_distance_to_the_center() {
+	let fill = Fill::Radial {
+		center: (0.5, 0.5),
+		radius: 0.5,
+		stops: vec![
+			(0.0, Color::rgb(255, 255, 255)),
+			(1.0, Color::rgba(0, 0, 0, 0)),
+		],
+	};
+
+	assert_close(fill.color_at(0.5, 0.5), [1.0; 4]);
+	assert_close(fill.color_at(0.5, 0.25), [0.5; 4]);
+	assert_close(fill.color_at(0.25, 0.5), [0.5; 4]);
+	assert_close(fill.color_at(0.0, 0.0), [0.0; 4]);
+}
@@ -0,0 +1,289 @@
+mod common;
+
+use std::{cell::RefCell, rc::Rc};
+
+use common::{layout, prepared, Square};
+use kitsune_ui::{
+	context::Context,
+	event::{ElementState, Event, Key, Modifiers, MouseButton, PointerEvent, PointerKind},
+	focus::{self, Focus},
+	layout::{Layout, LayoutContext},
+	view::{PhysicalPosition, SizeHint, View},
+	widget::{Column, Row, ScrollAxis, VirtualList, Visitor, Widget, WidgetContext},
+};
+
+type Log = Rc<RefCell<Vec<String>>>;
+
+/// A focusable widget that records when it gains or loses focus, and the characters it receives.
+struct Field<T> {
+	name: &'static str,
+	log: Log,
+	value: T,
+}
+
+impl<T: Widget> Widget for Field<T> {
+	type Renderable = T::Renderable;
+
+	fn layout(&self, context: &Context<LayoutContext>, view: View) -> Layout {
+		let child = self
+			.value
+			.layout(context, view.clone());
+		Layout::new(view, vec![child])
+	}
+
+	fn get_renderable(
+		&mut self,
+		context: &mut Context<WidgetContext>,
+		layout: &Layout,
+	) -> Self::Renderable {
+		self.value
+			.get_renderable(context, &layout.children()[0])
+	}
+
+	fn width_hint(&self, context: &Context<LayoutContext>, view: &View) -> SizeHint {
+		self.value
+			.width_hint(context, view)
+	}
+
+	fn height_hint(&self, context: &Context<LayoutContext>, view: &View) -> SizeHint {
+		self.value
+			.height_hint(context, view)
+	}
+
+	fn focusable(&self) -> bool {
+		true
+	}
+
+	fn focus_changed(&mut self, focused: bool) {
+		let change = if focused { "gained" } else { "lost" };
+		self.log
+			.borrow_mut()
+			.push(format!("{} {change}", self.name));
+	}
+
+	fn handle_key(&mut self, event: &Event) {
+		if let Event::Text(character) = event {
+			self.log
+				.borrow_mut()
+				.push(format!("{} {character}", self.name));
+		}
+	}
+
+	fn visit_children<V: Visitor>(&mut self, layout: &Layout, visitor: &mut V) {
+		visitor.visit(&mut self.value, &layout.children()[0]);
+	}
+}
+
+fn field<T>(name: &'static str, log: &Log, value: T) -> Field<T> {
+	Field {
+		name,
+		log: log.clone(),
+		value,
+	}
+}
+
+fn take(log: &Log) -> Vec<String> {
+	log.borrow_mut()
+		.drain(..)
+		.collect()
+}
+
+fn tab(state: ElementState) -> Event {
+	Event::Keyboard {
+		key: Key::Tab,
+		state,
+	}
+}
+
+fn click(x: f64, y: f64) -> PointerEvent {
+	let kind = PointerKind::Button {
+		button: MouseButton::Left,
+		state: ElementState::Pressed,
+	};
+	PointerEvent::new(kind, PhysicalPosition::new(x, y), None)
+}
+
+#[test]
+fn focus_moves_in_tree_order() {
+	let log = Log::default();
+	let mut widget = Column::new(vec![
+		field("a", &log, Square(10, 10)),
+		field("b", &log, Square(10, 10)),
+	]);
+	let mut nested = (
+		field("outer", &log, field("inner", &log, Square(10, 10))),
+		&mut widget,
+	);
+	let layout = layout(&nested, 200, 100);
+	let mut focus = Focus::new();
+
+	assert_eq!(focus::count(&mut nested, &layout), 4);
+
+	for _ in 0..5 {
+		focus.next(&mut nested, &layout);
+	}
+	assert_eq!(
+		take(&log),
+		vec![
+			"outer gained",
+			"outer lost",
+			"inner gained",
+			"inner lost",
+			"a gained",
+			"a lost",
+			"b gained",
+			"b lost",
+			"outer gained"
+		]
+	);
+
+	focus.previous(&mut nested, &layout);
+	assert_eq!(take(&log), vec!["outer lost", "b gained"]);
+	assert_eq!(focus.focused(&mut nested, &layout), Some(3));
+}
+
+#[test]
+fn keys_only_reach_the_focused_widget() {
+	let log = Log::default();
+	let mut widget = Column::new(vec![
+		field("a", &log, Square(10, 10)),
+		field("b", &log, Square(10, 10)),
+	]);
+	let layout = layout(&widget, 200, 100);
+	let mut focus = Focus::new();
+
+	focus.handle_key(&mut widget, &layout, &Event::Text('x'));
+	focus.focus(&mut widget, &layout, Some(1));
+	focus.handle_key(&mut widget, &layout, &Event::Text('y'));
+
+	assert_eq!(take(&log), vec!["b gained", "b y"]);
+}
+
+#[test]
+fn tab_and_shift_tab_move_focus() {
+	let log = Log::default();
+	let mut widget = Column::new(vec![
+		field("a", &log, Square(10, 10)),
+		field("b", &log, Square(10, 10)),
+	]);
+	let layout = layout(&widget, 200, 100);
+	let mut focus = Focus::new();
+
+	focus.handle_key(&mut widget, &layout, &tab(ElementState::Pressed));
+	focus.handle_key(&mut widget, &layout, &tab(ElementState::Released));
+	focus.handle_key(&mut widget, &layout, &Event::Text('\t'));
+	focus.handle_key(&mut widget, &layout, &tab(ElementState::Pressed));
+	assert_eq!(take(&log), vec!["a gained", "a lost", "b gained"]);
+
+	let shift = Event::ModifiersChanged(Modifiers {
+		shift: true,
+		..Modifiers::default()
+	});
+	focus.handle_key(&mut widget, &layout, &shift);
+	focus.handle_key(&mut widget, &layout, &tab(ElementState::Pressed));
+	assert_eq!(take(&log), vec!["b lost", "a gained"]);
+}
+
+#[test]
+fn clicking_focuses_the_innermost_widget_under_the_cursor() {
+	let log = Log::default();
+	let mut widget = Row::new(vec![
+		field(
+			"a",
+			&log,
+			field("x", &log, Square(30, 30)).padded(10, 10, 10, 10),
+		),
+		field(
+			"b",
+			&log,
+			field("c", &log, Square(20, 20)).padded(10, 10, 10, 10),
+		),
+	])
+	.spaced(10);
+	let layout = layout(&widget, 200, 100);
+	let mut focus = Focus::new();
+
+	focus.handle_pointer(&mut widget, &layout, &click(5.0, 5.0));
+	focus.handle_pointer(&mut widget, &layout, &click(75.0, 25.0));
+	focus.handle_pointer(&mut widget, &layout, &click(62.0, 25.0));
+	focus.handle_pointer(&mut widget, &layout, &click(190.0, 90.0));
+
+	assert_eq!(
+		take(&log),
+		vec!["a gained", "a lost", "c gained", "c lost", "b gained", "b lost"]
+	);
+	assert_eq!(focus.focused(&mut widget, &layout), None);
+}
+
+#[test]
+fn clicking_scrolled_away_widgets_does_not_focus_them() {
+	let log = Log::default();
+	let mut widget = Column::new(vec![
+		field("a", &log, Square(50, 150)),
+		field("b", &log, Square(50, 150)),
+	])
+	.scrollable(ScrollAxis::Vertical)
+	.padded(0, 0, 50, 0);
+	let layout = layout(&widget, 200, 100);
+	let mut focus = Focus::new();
+
+	// `a` continues below the bottom of the scroll, where it is not visible.
+	focus.handle_pointer(&mut widget, &layout, &click(20.0, 70.0));
+	assert_eq!(take(&log), Vec::<String>::new());
+
+	widget.scroll_to(PhysicalPosition::new(0.0, 130.0));
+	let layout = self::layout(&widget, 200, 100);
+	focus.handle_pointer(&mut widget, &layout, &click(20.0, 30.0));
+	assert_eq!(take(&log), vec!["b gained"]);
+}
+
+#[test]
+fn focus_is_removed_with_the_focused_widget() {
+	let log = Log::default();
+	let mut widget = Column::new(vec![
+		field("a", &log, Square(10, 10)),
+		field("b", &log, Square(10, 10)),
+	]);
+	let layout = layout(&widget, 200, 100);
+	let mut focus = Focus::new();
+	focus.focus(&mut widget, &layout, Some(1));
+
+	widget.pop();
+	let layout = self::layout(&widget, 200, 100);
+	focus.update(&mut widget, &layout);
+	focus.handle_key(&mut widget, &layout, &Event::Text('x'));
+
+	assert_eq!(take(&log), vec!["b gained"]);
+	assert_eq!(focus.focused(&mut widget, &layout), None);
+}
+
+#[test]
+fn focus_stays_with_values_of_a_virtual_list_while_it_scrolls() {
+	const NAMES: [&str; 10] = ["0", "1", "2", "3", "4", "5", "6", "7", "8", "9"];
+
+	let log = Log::default();
+	let built = log.clone();
+	let mut list = VirtualList::new(10, 20, move |index, _| {
+		field(NAMES[index], &built, Square(10, 20))
+	});
+	let layout = prepared(&mut list, 200, 100);
+	let mut focus = Focus::new();
+	focus.focus(&mut list, &layout, Some(2));
+	assert_eq!(take(&log), vec!["2 gained"]);
+
+	// The first value scrolls out of view, which moves the focused one to the front.
+	list.scroll_to(20.0);
+	let layout = prepared(&mut list, 200, 100);
+	focus.update(&mut list, &layout);
+	focus.handle_key(&mut list, &layout, &Event::Text('x'));
+	assert_eq!(take(&log), vec!["0 lost", "2 x"]);
+	assert_eq!(focus.focused(&mut list, &layout), Some(1));
+
+	// Once the focused value scrolls out of view it loses focus.
+	list.scroll_to(100.0);
+	let layout = prepared(&mut list, 200, 100);
+	focus.update(&mut list, &layout);
+	focus.handle_key(&mut list, &layout, &Event::Text('y'));
+	assert_eq!(take(&log), vec!["1 lost", "2 lost", "3 lost", "4 lost"]);
+	assert_eq!(focus.focused(&mut list, &layout), None);
+}
@@ -0,0 +1,71 @@
+use kitsune_ui::{
+	color::Color,
+	context::Context,
+	headless::{self, HeadlessRenderer},
+	layout::LayoutContext,
+	text::{Font, Wrap},
+	view::{GlobalView, PhysicalPosition, PhysicalSize},
+	widget::{Column, TextInput, Widget},
+};
+
+async fn renderer() -> HeadlessRenderer {
+	match HeadlessRenderer::new(true).await {
+		Err(headless::Error::AdapterNotFound) => HeadlessRenderer::new(false).await,
+		renderer => renderer,
+	}
+	.expect("could not create a headless renderer")
+}
+
+#[tokio::test]
+async fn glyphs_that_do_not_fit_in_the_atlas_are_still_drawn() {
+	let mut renderer = renderer().await;
+	renderer.set_background(Color::WHITE);
+	let size = PhysicalSize::new(2000, 2000);
+
+	// More glyphs than fit in the atlas at once, all of them rasterized before the last line.
+	let glyphs = ('A'..='Z')
+		.chain('a'..='z')
+		.chain('À'..='ſ')
+		.chain('Α'..='ω')
+		.chain('Ѐ'..='џ')
+		.collect::<Vec<_>>();
+	let words = glyphs
+		.chunks(20)
+		.map(|x| x.iter().collect::<String>())
+		.collect::<Vec<_>>();
+	let mut widget = Column::new(vec![words.join(" "), String::from("0123456789")]);
+
+	let font = Font::roboto().unwrap();
+	let layout = widget.layout(
+		&Context::new(LayoutContext::new(&font)),
+		GlobalView::new(size).view(size, PhysicalPosition::new(0, 0)),
+	);
+	let last_line = layout.children()[1].view();
+
+	let image = renderer
+		.render(&mut widget, size)
+		.unwrap();
+	let (left, top) = (last_line.position().x as u32, last_line.position().y as u32);
+	let drawn = (top..top + last_line.height())
+		.flat_map(|y| (left..left + last_line.width()).map(move |x| (x, y)))
+		.filter(|&(x, y)| image.pixel(x, y)[0] < 128)
+		.count();
+	assert!(
+		drawn > 2_000,
+		"only {drawn} pixels of the last line were drawn"
+	);
+}
+
+#[tokio::test]
+async fn selections_of_text_that_is_left_out_are_drawn() {
+	let renderer = renderer().await;
+	let mut input = TextInput::new("one two three four five six");
+	input.select_all();
+	let mut widget = input
+		.wrapped(Wrap::Word)
+		.max_lines(1);
+
+	renderer
+		.render(&mut widget, PhysicalSize::new(300, 200))
+		.unwrap();
+}
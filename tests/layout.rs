@@ -0,0 +1,790 @@
+mod common;
+
+use common::{layout, prepared};
+use kitsune_ui::{
+	context::Context,
+	layout::{Align, Anchor, Justify, Layout, LayoutContext, Track, DEFAULT_FONT_SIZE},
+	render::RenderedMesh,
+	text::{Font, Wrap},
+	view::{GlobalView, PhysicalPosition, PhysicalSize, SizeHint, View},
+	widget::{
+		Cell, Column, Grid, Layer, Row, Scroll, ScrollAxis, Stack, VirtualList, Widget,
+		WidgetContext, WrappingRow,
+	},
+};
+
+/// A widget with a fixed width and height hint.
+#[derive(Clone)]
+struct Hinted(SizeHint, SizeHint);
+
+impl Widget for Hinted {
+	type Renderable = Option<RenderedMesh>;
+
+	fn layout(&self, context: &Context<LayoutContext>, view: View) -> Layout {
+		let width = self.width_hint(context, &view);
+		let height = self.height_hint(context, &view);
+		Layout::leaf(view.from_size_hints(width, height))
+	}
+
+	fn get_renderable(&mut self, _: &mut Context<WidgetContext>, _: &Layout) -> Self::Renderable {
+		None
+	}
+
+	fn width_hint(&self, _: &Context<LayoutContext>, _: &View) -> SizeHint {
+		self.0.clone()
+	}
+
+	fn height_hint(&self, _: &Context<LayoutContext>, _: &View) -> SizeHint {
+		self.1.clone()
+	}
+}
+
+fn glyph_size(value: char) -> PhysicalSize<u32> {
+	sized_glyph(value, DEFAULT_FONT_SIZE)
+}
+
+fn sized_glyph(value: char, font_size: f32) -> PhysicalSize<u32> {
+	Font::roboto()
+		.unwrap()
+		.glyph(value, font_size)
+		.size()
+}
+
+fn offset(view: &View) -> (u32, u32) {
+	(view.offset().x, view.offset().y)
+}
+
+#[test]
+fn char_takes_the_size_of_its_glyph() {
+	let layout = layout(&'a', 500, 500);
+
+	assert_eq!(layout.view().size(), glyph_size('a'));
+	assert!(layout.children().is_empty());
+}
+
+#[test]
+fn spaces_take_up_the_advance_of_their_glyph() {
+	let layout = layout(&' ', 500, 500);
+
+	assert!(layout.view().width() > 0);
+	assert_eq!(layout.view().size(), glyph_size(' '));
+}
+
+#[test]
+fn characters_share_a_line_height_and_baseline() {
+	let font = Font::roboto().unwrap();
+	let context = Context::new(LayoutContext::new(&font));
+	let view = GlobalView::new(PhysicalSize::new(500, 500))
+		.view(PhysicalSize::new(500, 500), PhysicalPosition::new(0, 0));
+
+	for value in ['a', 'g', 'T', '.'] {
+		assert_eq!(
+			layout(&value, 500, 500)
+				.view()
+				.height(),
+			glyph_size('x').height
+		);
+		assert_eq!(
+			value.baseline(&context, &view),
+			'x'.baseline(&context, &view)
+		);
+	}
+}
+
+#[test]
+fn text_is_kerned() {
+	let font = Font::roboto().unwrap();
+	let (a, v) = (
+		font.glyph('A', DEFAULT_FONT_SIZE),
+		font.glyph('V', DEFAULT_FONT_SIZE),
+	);
+	assert!(font.kern(&a, &v) < 0.0);
+
+	let text = layout(&String::from("AV"), 500, 500);
+	let characters = text.children();
+
+	assert!(characters[0].view().width() < glyph_size('A').width);
+	assert_eq!(
+		characters[1].view().offset().x,
+		characters[0].view().width()
+	);
+}
+
+#[test]
+fn text_that_is_left_out_takes_up_no_space_after_the_last_line() {
+	let text = "one two three four five six";
+	let widget = String::from(text)
+		.wrapped(Wrap::Word)
+		.max_lines(1);
+
+	let layout = layout(&widget, 500, 500);
+	let characters = layout.children();
+
+	assert_eq!(characters.len(), text.chars().count());
+	let last = characters.last().unwrap().view();
+	assert_eq!(last.width(), 0);
+	assert_eq!(last.offset().y, 0);
+}
+
+#[test]
+fn font_size_scales_the_text_of_its_children() {
+	let row = layout(&Row::new(vec!['a', 'b']).font_size(20.0), 500, 500);
+
+	assert_eq!(row.children()[0].view().size(), sized_glyph('a', 20.0));
+	assert_eq!(
+		offset(row.children()[1].view()),
+		(sized_glyph('a', 20.0).width, 0)
+	);
+
+	let nested = layout(&'a'.font_size(40.0).font_size(20.0), 500, 500);
+	assert_eq!(nested.view().size(), sized_glyph('a', 40.0));
+}
+
+#[test]
+fn text_styles_only_change_what_they_set() {
+	let text = layout(
+		&String::from("a")
+			.wrapped(Wrap::None)
+			.font_size(20.0),
+		500,
+		500,
+	);
+
+	assert_eq!(
+		text.children()[0].view().height(),
+		sized_glyph('a', 20.0).height
+	);
+}
+
+#[test]
+fn text_is_scaled_by_the_scale_factor() {
+	let font = Font::roboto().unwrap();
+	let context = LayoutContext::new(&font)
+		.with_font_size(20.0)
+		.with_scale_factor(2.0);
+
+	let size = PhysicalSize::new(500, 500);
+	let layout = 'a'.layout(
+		&Context::new(context),
+		GlobalView::new(size).view(size, PhysicalPosition::new(0, 0)),
+	);
+
+	assert_eq!(layout.view().size(), sized_glyph('a', 40.0));
+}
+
+#[test]
+fn bordered_insets_its_child() {
+	let layout = layout(&'a'.bordered(10), 500, 500);
+
+	let glyph = glyph_size('a');
+	assert_eq!(
+		layout.view().size(),
+		PhysicalSize::new(glyph.width + 20, glyph.height + 20)
+	);
+
+	let child = &layout.children()[0];
+	assert_eq!(offset(child.view()), (10, 10));
+	assert_eq!(child.view().size(), glyph);
+}
+
+#[test]
+fn row_places_children_next_to_each_other() {
+	let layout = layout(&Row::new(vec!['a', 'b', 'c']), 500, 500);
+
+	let children = layout.children();
+	assert_eq!(children.len(), 3);
+	assert_eq!(offset(children[0].view()), (0, 0));
+	assert_eq!(offset(children[1].view()), (glyph_size('a').width, 0));
+	assert_eq!(
+		offset(children[2].view()),
+		(glyph_size('a').width + glyph_size('b').width, 0)
+	);
+}
+
+#[test]
+fn column_places_children_below_each_other() {
+	let layout = layout(&Column::new(vec!['a', 'b']), 500, 500);
+
+	let children = layout.children();
+	assert_eq!(offset(children[0].view()), (0, 0));
+	assert_eq!(offset(children[1].view()), (0, glyph_size('a').height));
+}
+
+#[test]
+fn wrapping_row_wraps_when_out_of_space() {
+	let width = glyph_size('m').width * 2 + 1;
+	let layout = layout(&WrappingRow::new(vec!['m'; 5]), width, 500);
+
+	let rows = layout
+		.children()
+		.iter()
+		.map(|x| x.view().offset().y)
+		.collect::<Vec<_>>();
+	let height = glyph_size('m').height;
+	assert_eq!(rows, vec![0, 0, height, height, 2 * height]);
+}
+
+#[test]
+fn wrapping_row_keeps_values_wider_than_the_view() {
+	let layout = layout(&WrappingRow::new(vec!['m', 'm']), 1, 500);
+
+	assert_eq!(layout.children().len(), 2);
+	assert_eq!(
+		layout.children()[1]
+			.view()
+			.offset()
+			.y,
+		glyph_size('m').height
+	);
+}
+
+#[test]
+fn tuples_overlay_their_children() {
+	let layout = layout(&('a', 'b'.bordered(5)), 500, 500);
+
+	let children = layout.children();
+	assert_eq!(children.len(), 2);
+	assert_eq!(offset(children[0].view()), (0, 0));
+	assert_eq!(offset(children[1].view()), (0, 0));
+}
+
+fn widths(layout: &Layout) -> Vec<u32> {
+	layout
+		.children()
+		.iter()
+		.map(|x| x.view().width())
+		.collect()
+}
+
+#[test]
+fn widgets_without_a_size_take_the_remaining_space() {
+	let layout = layout(
+		&Row::new(vec![
+			Hinted(SizeHint::Physical(100), SizeHint::None),
+			Hinted(SizeHint::None, SizeHint::None),
+		]),
+		500,
+		500,
+	);
+
+	assert_eq!(widths(&layout), vec![100, 400]);
+	assert_eq!(offset(layout.children()[1].view()), (100, 0));
+}
+
+#[test]
+fn flexible_widgets_share_space_by_weight() {
+	let layout = layout(
+		&Row::new(vec![
+			Hinted(SizeHint::Flex(1.0), SizeHint::None),
+			Hinted(SizeHint::Physical(200), SizeHint::None),
+			Hinted(SizeHint::Flex(2.0), SizeHint::None),
+		]),
+		500,
+		500,
+	);
+
+	assert_eq!(widths(&layout), vec![100, 200, 200]);
+}
+
+#[test]
+fn flexible_widgets_respect_their_limits() {
+	let layout = layout(
+		&Row::new(vec![
+			Hinted(
+				SizeHint::Min(vec![SizeHint::Flex(1.0), SizeHint::Physical(50)]),
+				SizeHint::None,
+			),
+			Hinted(SizeHint::Flex(1.0), SizeHint::None),
+			Hinted(
+				SizeHint::Max(vec![SizeHint::Flex(1.0), SizeHint::Physical(300)]),
+				SizeHint::None,
+			),
+		]),
+		500,
+		500,
+	);
+
+	assert_eq!(widths(&layout), vec![50, 150, 300]);
+}
+
+#[test]
+fn overflowing_widgets_are_shrunk() {
+	let layout = layout(
+		&Row::new(vec![
+			Hinted(SizeHint::Physical(300), SizeHint::None),
+			Hinted(SizeHint::Flex(1.0), SizeHint::None),
+			Hinted(SizeHint::Physical(100), SizeHint::None),
+		]),
+		200,
+		500,
+	);
+
+	assert_eq!(widths(&layout), vec![150, 0, 50]);
+}
+
+#[test]
+fn flex_width_stretches_the_wrappers_around_it() {
+	let layout = layout(
+		&Row::new(vec![
+			'a'.flex_width(1.0).bordered(5),
+			'b'.flex_width(3.0).bordered(5),
+		]),
+		400,
+		500,
+	);
+
+	// Both characters are narrower than their share, so the space is divided by weight alone.
+	assert_eq!(widths(&layout), vec![10 + 95, 10 + 285]);
+}
+
+#[test]
+fn flexible_heights_in_a_column() {
+	let layout = layout(
+		&Column::new(vec!['a'.flex_height(1.0), 'b'.flex_height(1.0)]),
+		500,
+		500,
+	);
+
+	let heights = layout
+		.children()
+		.iter()
+		.map(|x| x.view().offset().y)
+		.collect::<Vec<_>>();
+	assert_eq!(heights, vec![0, 250]);
+}
+
+fn fixed(width: u32, height: u32) -> Hinted {
+	Hinted(SizeHint::Physical(width), SizeHint::Physical(height))
+}
+
+fn offsets(layout: &Layout) -> Vec<(u32, u32)> {
+	layout
+		.children()
+		.iter()
+		.map(|x| offset(x.view()))
+		.collect()
+}
+
+#[test]
+fn rows_justify_the_remaining_space() {
+	let xs = |justify| {
+		let row = Row::new(vec![fixed(100, 10), fixed(100, 10), fixed(100, 10)]).justified(justify);
+
+		offsets(&layout(&row, 600, 100))
+			.into_iter()
+			.map(|(x, _)| x)
+			.collect::<Vec<_>>()
+	};
+
+	assert_eq!(xs(Justify::Start), vec![0, 100, 200]);
+	assert_eq!(xs(Justify::Center), vec![150, 250, 350]);
+	assert_eq!(xs(Justify::End), vec![300, 400, 500]);
+	assert_eq!(xs(Justify::SpaceBetween), vec![0, 250, 500]);
+	assert_eq!(xs(Justify::SpaceAround), vec![50, 250, 450]);
+	assert_eq!(xs(Justify::SpaceEvenly), vec![75, 250, 425]);
+}
+
+#[test]
+fn rows_align_their_values_vertically() {
+	let child = |align| {
+		let row = Row::new(vec![
+			Column::new(vec![fixed(10, 100)]).flex_height(1.0),
+			Column::new(vec![fixed(10, 40)]).flex_height(1.0),
+		])
+		.aligned(align);
+
+		layout(&row, 500, 100).children()[1]
+			.view()
+			.clone()
+	};
+
+	assert_eq!(offset(&child(Align::Start)), (10, 0));
+	assert_eq!(offset(&child(Align::Center)), (10, 30));
+	assert_eq!(offset(&child(Align::End)), (10, 60));
+	assert_eq!(child(Align::Start).height(), 40);
+	assert_eq!(offset(&child(Align::Stretch)), (10, 0));
+	assert_eq!(child(Align::Stretch).height(), 100);
+}
+
+#[test]
+fn only_values_without_a_height_fill_rows_that_do_not_stretch() {
+	let heights = |align| {
+		let row = Row::new(vec![
+			Hinted(SizeHint::Physical(10), SizeHint::None),
+			Hinted(SizeHint::Physical(10), SizeHint::Flex(1.0)),
+			Hinted(
+				SizeHint::Physical(10),
+				SizeHint::Max(vec![SizeHint::Physical(40), SizeHint::Flex(1.0)]),
+			),
+		])
+		.aligned(align);
+
+		layout(&row, 500, 100)
+			.children()
+			.iter()
+			.map(|x| x.view().height())
+			.collect::<Vec<_>>()
+	};
+
+	assert_eq!(heights(Align::Start), vec![100, 100, 40]);
+	assert_eq!(heights(Align::Stretch), vec![100, 100, 100]);
+}
+
+#[test]
+fn rows_align_the_baselines_of_text() {
+	// The border without a width only sizes the row to the height it asks for.
+	let layout = layout(
+		&Row::new(vec!['a'.bordered(10), 'a'.bordered(0)])
+			.aligned(Align::Baseline)
+			.bordered(0),
+		500,
+		500,
+	);
+
+	assert_eq!(layout.view().height(), glyph_size('a').height + 20);
+	assert_eq!(
+		offsets(&layout.children()[0]),
+		vec![(0, 0), (glyph_size('a').width + 20, 10)]
+	);
+}
+
+#[test]
+fn columns_justify_and_align() {
+	let layout = layout(
+		&Column::new(vec![fixed(100, 100), fixed(50, 100)])
+			.justified(Justify::End)
+			.aligned(Align::Center),
+		100,
+		500,
+	);
+
+	assert_eq!(offsets(&layout), vec![(0, 300), (25, 400)]);
+}
+
+#[test]
+fn padding_insets_the_child_and_adds_to_the_size() {
+	let layout = layout(&'a'.padded(1, 2, 3, 4).bordered(0), 500, 500);
+
+	let glyph = glyph_size('a');
+	assert_eq!(
+		layout.view().size(),
+		PhysicalSize::new(glyph.width + 6, glyph.height + 4)
+	);
+
+	let padded = &layout.children()[0];
+	assert_eq!(offset(padded.children()[0].view()), (4, 1));
+	assert_eq!(padded.children()[0].view().size(), glyph);
+}
+
+#[test]
+fn gaps_separate_values() {
+	let row = layout(
+		&Row::new(vec![fixed(10, 10), fixed(20, 10), fixed(30, 10)]).spaced(5),
+		500,
+		500,
+	);
+	assert_eq!(offsets(&row), vec![(0, 0), (15, 0), (40, 0)]);
+
+	let column = layout(
+		&Column::new(vec![fixed(10, 10), fixed(10, 20), fixed(10, 30)]).spaced(5),
+		500,
+		500,
+	);
+	assert_eq!(offsets(&column), vec![(0, 0), (0, 15), (0, 40)]);
+}
+
+#[test]
+fn gaps_are_part_of_the_size() {
+	let layout = layout(
+		&Row::new(vec![fixed(10, 10), fixed(10, 10)])
+			.spaced(7)
+			.bordered(0),
+		500,
+		500,
+	);
+
+	assert_eq!(layout.view().width(), 27);
+}
+
+#[test]
+fn gaps_separate_wrapped_lines() {
+	let layout = layout(&WrappingRow::new(vec![fixed(10, 10); 3]).spaced(5), 30, 500);
+
+	assert_eq!(offsets(&layout), vec![(0, 0), (15, 0), (0, 15)]);
+}
+
+fn sizes(layout: &Layout) -> Vec<(u32, u32)> {
+	layout
+		.children()
+		.iter()
+		.map(|x| (x.view().width(), x.view().height()))
+		.collect()
+}
+
+#[test]
+fn grid_tracks_of_every_kind() {
+	let grid = Grid::new(
+		vec![
+			Cell::new(fixed(10, 10), 0, 0),
+			Cell::new(fixed(10, 10), 0, 1),
+			Cell::new(fixed(30, 20), 0, 2),
+			Cell::new(fixed(10, 10), 1, 3),
+		],
+		vec![
+			Track::Physical(50),
+			Track::Virtual(0.1),
+			Track::Auto,
+			Track::Flex(1.0),
+		],
+		vec![Track::Auto, Track::Flex(1.0)],
+	);
+	let layout = layout(&grid, 200, 100);
+
+	assert_eq!(offsets(&layout), vec![(0, 0), (50, 0), (70, 0), (100, 20)]);
+	assert_eq!(sizes(&layout), vec![(10, 10), (10, 10), (30, 20), (10, 10)]);
+}
+
+#[test]
+fn grid_cells_span_tracks_and_gaps() {
+	let stretched = Hinted(SizeHint::Flex(1.0), SizeHint::Flex(1.0));
+	let grid = Grid::new(
+		vec![
+			Cell::new(stretched.clone(), 0, 0).spanning(1, 2),
+			Cell::new(stretched.clone(), 0, 2).spanning(2, 1),
+			Cell::new(stretched, 1, 0),
+		],
+		vec![Track::Physical(20); 3],
+		vec![Track::Physical(10); 2],
+	)
+	.spaced(5);
+	let layout = layout(&grid, 200, 100);
+
+	assert_eq!(offsets(&layout), vec![(0, 0), (50, 0), (0, 15)]);
+	assert_eq!(sizes(&layout), vec![(45, 10), (20, 25), (20, 10)]);
+}
+
+#[test]
+fn grid_cells_outside_of_the_tracks_are_hidden() {
+	let grid = Grid::new(
+		vec![Cell::new(fixed(10, 10), 0, 5)],
+		vec![Track::Auto],
+		vec![Track::Auto],
+	);
+	let layout = layout(&grid, 200, 100);
+
+	assert_eq!(sizes(&layout), vec![(0, 0)]);
+}
+
+#[test]
+fn grid_size_is_the_sum_of_its_tracks() {
+	let grid = Grid::new(
+		vec![Cell::new(fixed(30, 40), 1, 1)],
+		vec![Track::Physical(10), Track::Auto],
+		vec![Track::Physical(10), Track::Auto],
+	)
+	.spaced(5)
+	.bordered(0);
+	let layout = layout(&grid, 200, 200);
+
+	assert_eq!(layout.view().size(), PhysicalSize::new(45, 55));
+}
+
+#[test]
+fn stack_places_layers_at_their_anchors() {
+	let anchors = [
+		Anchor::TopLeft,
+		Anchor::Top,
+		Anchor::TopRight,
+		Anchor::Left,
+		Anchor::Center,
+		Anchor::Right,
+		Anchor::BottomLeft,
+		Anchor::Bottom,
+		Anchor::BottomRight,
+	];
+	let stack = Stack::new(
+		anchors
+			.into_iter()
+			.map(|x| Layer::new(fixed(10, 20), x))
+			.collect(),
+	);
+	let layout = layout(&stack, 100, 60);
+
+	assert_eq!(
+		offsets(&layout),
+		vec![
+			(0, 0),
+			(45, 0),
+			(90, 0),
+			(0, 20),
+			(45, 20),
+			(90, 20),
+			(0, 40),
+			(45, 40),
+			(90, 40)
+		]
+	);
+	assert!(sizes(&layout)
+		.into_iter()
+		.all(|x| x == (10, 20)));
+}
+
+#[test]
+fn stack_shifts_layers_without_leaving_it() {
+	let stack = Stack::new(vec![
+		Layer::new(fixed(10, 10), Anchor::TopRight).shifted(-5, 5),
+		Layer::new(fixed(10, 10), Anchor::TopRight).shifted(5, -5),
+		Layer::new(fixed(10, 10), Anchor::Center).shifted(-100, 100),
+	]);
+	let layout = layout(&stack, 100, 60);
+
+	assert_eq!(offsets(&layout), vec![(85, 5), (90, 0), (0, 50)]);
+}
+
+#[test]
+fn stack_layers_without_a_size_fill_it() {
+	let stack = Stack::new(vec![
+		Layer::new(Hinted(SizeHint::None, SizeHint::Flex(1.0)), Anchor::Center),
+		Layer::new(fixed(200, 10), Anchor::Center),
+	]);
+	let layout = layout(&stack, 100, 60);
+
+	assert_eq!(sizes(&layout), vec![(100, 60), (100, 10)]);
+	assert_eq!(offsets(&layout), vec![(0, 0), (0, 25)]);
+}
+
+fn positions(layout: &Layout) -> Vec<(i32, i32)> {
+	layout
+		.children()
+		.iter()
+		.map(|x| (x.view().position().x, x.view().position().y))
+		.collect()
+}
+
+fn scrolled_column(offset: f32) -> Scroll<Column<Hinted>> {
+	let mut scroll = Column::new(
+		(0..10)
+			.map(|_| fixed(10, 30))
+			.collect(),
+	)
+	.scrollable(ScrollAxis::Vertical);
+	scroll.scroll_to(PhysicalPosition::new(20.0, offset));
+	scroll
+}
+
+#[test]
+fn scroll_lays_out_its_value_beyond_its_view() {
+	let layout = layout(&scrolled_column(50.0), 100, 100);
+	let content = &layout.children()[0];
+
+	assert_eq!(layout.view().size(), PhysicalSize::new(100, 100));
+	assert_eq!(content.view().size(), PhysicalSize::new(100, 300));
+	assert_eq!(offsets(content)[9], (0, 270));
+	assert_eq!(positions(content)[..3], [(0, -50), (0, -20), (0, 10)]);
+}
+
+#[test]
+fn scroll_does_not_go_past_the_end_of_its_value() {
+	let layout = layout(&scrolled_column(1000.0), 100, 100);
+
+	assert_eq!(positions(&layout.children()[0])[9], (0, 70));
+}
+
+#[test]
+fn scroll_only_moves_along_its_axis() {
+	let scroll = scrolled_column(0.0);
+	assert_eq!(scroll.offset(), PhysicalPosition::new(0.0, 0.0));
+
+	let layout = layout(
+		&fixed(300, 300).scrollable(ScrollAxis::Horizontal),
+		100,
+		100,
+	);
+	assert_eq!(
+		layout.children()[0].view().size(),
+		PhysicalSize::new(300, 100)
+	);
+}
+
+/// A list of 1000 items that records which indices were built, and whether a value was reused.
+fn virtual_list(
+	built: &std::rc::Rc<std::cell::RefCell<Vec<(usize, bool)>>>,
+) -> VirtualList<Hinted, impl FnMut(usize, Option<Hinted>) -> Hinted> {
+	let built = built.clone();
+	VirtualList::new(1000, 20, move |index, recycled| {
+		built
+			.borrow_mut()
+			.push((index, recycled.is_some()));
+		recycled.unwrap_or_else(|| fixed(50, 20))
+	})
+}
+
+#[test]
+fn virtual_list_only_builds_visible_items() {
+	let built = Default::default();
+	let mut list = virtual_list(&built);
+	let layout = prepared(&mut list, 100, 50);
+
+	assert_eq!(*built.borrow(), vec![(0, false), (1, false), (2, false)]);
+	assert_eq!(offsets(&layout), vec![(0, 0), (0, 20), (0, 40)]);
+	assert_eq!(sizes(&layout), vec![(50, 20); 3]);
+}
+
+#[test]
+fn virtual_list_recycles_items_that_scroll_out_of_view() {
+	let built = Default::default();
+	let mut list = virtual_list(&built);
+	prepared(&mut list, 100, 50);
+	built.borrow_mut().clear();
+
+	list.scroll_to(30.0);
+	let layout = prepared(&mut list, 100, 50);
+
+	assert_eq!(*built.borrow(), vec![(3, true)]);
+	assert_eq!(offsets(&layout), vec![(0, 20), (0, 40), (0, 60)]);
+	assert_eq!(positions(&layout), vec![(0, -10), (0, 10), (0, 30)]);
+}
+
+#[test]
+fn virtual_list_does_not_scroll_past_its_last_item() {
+	let built = Default::default();
+	let mut list = virtual_list(&built);
+	list.scroll_to(1e9);
+	let layout = prepared(&mut list, 100, 50);
+
+	assert_eq!(
+		*built.borrow(),
+		vec![(997, false), (998, false), (999, false)]
+	);
+	assert_eq!(positions(&layout), vec![(0, -10), (0, 10), (0, 30)]);
+}
+
+#[test]
+fn virtual_list_only_builds_items_when_prepared() {
+	let built = Default::default();
+	let mut list = virtual_list(&built);
+
+	let first = layout(&list, 100, 50);
+	assert!(built.borrow().is_empty());
+	assert!(first.children().is_empty());
+
+	assert!(list.prepare(&first));
+	assert_eq!(built.borrow().len(), 3);
+
+	let second = layout(&list, 100, 50);
+	assert!(!list.prepare(&second));
+	assert_eq!(built.borrow().len(), 3);
+	assert_eq!(second.children().len(), 3);
+}
+
+#[test]
+fn wrapped_virtual_lists_are_prepared() {
+	let built = Default::default();
+	let mut list = virtual_list(&built)
+		.flex_height(1.0)
+		.cached();
+	let layout = prepared(&mut list, 100, 50);
+
+	assert_eq!(built.borrow().len(), 3);
+	assert_eq!(layout.children().len(), 3);
+}
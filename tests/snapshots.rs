@@ -0,0 +1,418 @@
+use kitsune_ui::{
+	color::Color,
+	context::Context,
+	event::{dispatch, ElementState, MouseButton, PointerEvent, PointerKind},
+	fill::Fill,
+	layout::{Align, Anchor, Justify, LayoutContext, Track},
+	shape::{BoxShadow, Radii},
+	testing::Snapshots,
+	text::{Font, Wrap},
+	view::{GlobalView, PhysicalPosition, PhysicalSize},
+	widget::{
+		Cell, Column, Grid, Layer, Rect, Row, ScrollAxis, Stack, TextInput, VirtualList, Widget,
+		WrappingRow,
+	},
+};
+
+async fn snapshots() -> Snapshots {
+	Snapshots::new(concat!(env!("CARGO_MANIFEST_DIR"), "/tests/snapshots"), 2)
+		.await
+		.expect("could not create a headless renderer")
+}
+
+#[tokio::test]
+async fn bordered_char() {
+	snapshots().await.assert(
+		"bordered_char",
+		&mut 'a'.bordered(10),
+		PhysicalSize::new(200, 200),
+	);
+}
+
+#[tokio::test]
+async fn nested_bordered() {
+	let mut widget = 'b'.bordered(5).bordered(15);
+
+	snapshots()
+		.await
+		.assert("nested_bordered", &mut widget, PhysicalSize::new(200, 200));
+}
+
+#[tokio::test]
+async fn bordered_row() {
+	let mut widget = Row::new(vec!['x'.bordered(4), 'y'.bordered(4)]).bordered(8);
+
+	snapshots()
+		.await
+		.assert("bordered_row", &mut widget, PhysicalSize::new(300, 200));
+}
+
+#[tokio::test]
+async fn wrapping_row() {
+	let mut widget = WrappingRow::new("wrapping".chars().collect());
+
+	snapshots()
+		.await
+		.assert("wrapping_row", &mut widget, PhysicalSize::new(300, 400));
+}
+
+#[tokio::test]
+async fn column_of_strings() {
+	let mut widget = Column::new(vec![String::from("ab"), String::from("cd")]);
+
+	snapshots().await.assert(
+		"column_of_strings",
+		&mut widget,
+		PhysicalSize::new(300, 300),
+	);
+}
+
+#[tokio::test]
+async fn flexible_row() {
+	let mut widget = Row::new(vec![
+		's'.flex_width(1.0).bordered(5),
+		'c'.flex_width(3.0).bordered(5),
+	]);
+
+	snapshots()
+		.await
+		.assert("flexible_row", &mut widget, PhysicalSize::new(400, 200));
+}
+
+#[tokio::test]
+async fn justified_baseline_row() {
+	let mut widget = Row::new(vec!['a'.bordered(4), 'g'.bordered(16), 'T'.bordered(8)])
+		.justified(Justify::SpaceEvenly)
+		.aligned(Align::Baseline);
+
+	snapshots().await.assert(
+		"justified_baseline_row",
+		&mut widget,
+		PhysicalSize::new(500, 200),
+	);
+}
+
+#[tokio::test]
+async fn padded_and_spaced() {
+	let row = |text: &str, padding: [u32; 4], gap| {
+		let [top, right, bottom, left] = padding;
+		Row::new(
+			text.chars()
+				.map(|x| {
+					x.padded(top, right, bottom, left)
+						.bordered(2)
+				})
+				.collect(),
+		)
+		.spaced(gap)
+	};
+
+	let mut widget = Column::new(vec![
+		row("abc", [0, 10, 0, 10], 12),
+		row("def", [10, 0, 10, 0], 4),
+	])
+	.spaced(20)
+	.padded(10, 10, 10, 10);
+
+	snapshots().await.assert(
+		"padded_and_spaced",
+		&mut widget,
+		PhysicalSize::new(400, 400),
+	);
+}
+
+#[tokio::test]
+async fn grid() {
+	let mut widget = Grid::new(
+		vec![
+			Cell::new(String::from("a").bordered(2), 0, 0),
+			Cell::new(String::from("wide").bordered(2), 0, 1).spanning(1, 2),
+			Cell::new(String::from("b").bordered(2), 1, 0).spanning(2, 1),
+			Cell::new(String::from("c").bordered(2), 1, 1),
+			Cell::new(String::from("d").bordered(2), 2, 2),
+		],
+		vec![Track::Auto, Track::Physical(80), Track::Flex(1.0)],
+		vec![Track::Auto, Track::Virtual(0.3), Track::Flex(1.0)],
+	)
+	.spaced(6)
+	.padded(10, 10, 10, 10);
+
+	snapshots()
+		.await
+		.assert("grid", &mut widget, PhysicalSize::new(400, 400));
+}
+
+#[tokio::test]
+async fn stack() {
+	let mut widget = Stack::new(vec![
+		// Added first, but drawn above the content.
+		Layer::new(String::from("!").bordered(3), Anchor::TopRight)
+			.shifted(-10, 10)
+			.layered(1),
+		Layer::new(String::from("content").bordered(6), Anchor::Center),
+		Layer::new(String::from("+").bordered(3), Anchor::BottomRight).shifted(-20, -20),
+	]);
+
+	snapshots()
+		.await
+		.assert("stack", &mut widget, PhysicalSize::new(400, 300));
+}
+
+#[tokio::test]
+async fn scroll() {
+	let lines = ["first", "second", "third", "fourth", "fifth"];
+	let mut scroll = Column::new(
+		lines
+			.into_iter()
+			.map(|x| String::from(x).bordered(2))
+			.collect(),
+	)
+	.scrollable(ScrollAxis::Vertical);
+	scroll.scroll_to(PhysicalPosition::new(0.0, 150.0));
+
+	let mut widget = scroll
+		.bordered(4)
+		.padded(40, 40, 40, 40);
+
+	snapshots()
+		.await
+		.assert("scroll", &mut widget, PhysicalSize::new(400, 300));
+}
+
+#[tokio::test]
+async fn virtual_list() {
+	let mut widget = VirtualList::new(10_000, 110, |index, _| index.to_string().bordered(2));
+	widget.scroll_to(9_000.0 * 110.0 + 50.0);
+
+	snapshots()
+		.await
+		.assert("virtual_list", &mut widget, PhysicalSize::new(300, 300));
+}
+
+#[tokio::test]
+async fn text_input() {
+	let mut focused = TextInput::new("caret");
+	focused.move_caret(2, false);
+	focused.focus_changed(true);
+
+	let mut selected = TextInput::new("select");
+	selected.move_caret(1, false);
+	selected.move_caret(4, true);
+
+	let mut widget = Column::new(vec![focused.bordered(2), selected.bordered(2)]).spaced(10);
+
+	snapshots()
+		.await
+		.assert("text_input", &mut widget, PhysicalSize::new(400, 300));
+}
+
+#[tokio::test]
+async fn buttons() {
+	let button = |label: &str| {
+		String::from(label)
+			.padded(10, 20, 10, 20)
+			.on_click(|| {})
+			.bordered(2)
+	};
+	let mut widget = Row::new(vec![button("idle"), button("over"), button("down")]).spaced(10);
+	let size = PhysicalSize::new(900, 200);
+
+	let font = Font::roboto().unwrap();
+	let layout = widget.layout(
+		&Context::new(LayoutContext::new(&font)),
+		GlobalView::new(size).view(size, PhysicalPosition::new(0, 0)),
+	);
+	let hover = layout.children()[1]
+		.view()
+		.position();
+	let press = layout.children()[2]
+		.view()
+		.position();
+	let at = |position: PhysicalPosition<i32>| {
+		PhysicalPosition::new(position.x as f64 + 10.0, position.y as f64 + 10.0)
+	};
+	dispatch(
+		&mut widget,
+		&layout,
+		&PointerEvent::new(PointerKind::Moved, at(hover), None),
+	);
+	let kind = PointerKind::Button {
+		button: MouseButton::Left,
+		state: ElementState::Pressed,
+	};
+	dispatch(
+		&mut widget,
+		&layout,
+		&PointerEvent::new(kind, at(press), None),
+	);
+
+	snapshots()
+		.await
+		.assert("buttons", &mut widget, size);
+}
+
+#[tokio::test]
+async fn fills() {
+	let mut widget = Row::new(vec![
+		Rect::new(Color::rgb(200, 40, 40)),
+		Rect::new(Fill::vertical(
+			Color::rgb(255, 255, 255),
+			Color::rgb(0, 0, 0),
+		)),
+		Rect::new(Fill::Radial {
+			center: (0.5, 0.5),
+			radius: 0.5,
+			stops: vec![
+				(0.0, Color::rgb(255, 220, 0)),
+				(0.5, Color::rgb(255, 0, 128)),
+				(1.0, Color::rgba(255, 0, 128, 0)),
+			],
+		}),
+	])
+	.spaced(10);
+
+	snapshots()
+		.await
+		.assert("fills", &mut widget, PhysicalSize::new(320, 100));
+}
+
+#[tokio::test]
+async fn rounded() {
+	let mut widget = Row::new(vec![
+		Rect::new(Color::rgb(200, 40, 40)).rounded(20.0),
+		Rect::new(Color::rgb(255, 255, 255))
+			.rounded(Radii::new(0.0, 30.0, 10.0, 60.0))
+			.outlined(4, Color::rgb(40, 40, 200)),
+		Rect::new(Fill::vertical(
+			Color::rgb(255, 220, 0),
+			Color::rgb(0, 160, 80),
+		))
+		.rounded(1000.0)
+		.outlined(6, Color::rgba(0, 0, 0, 128)),
+	])
+	.spaced(10)
+	.padded(10, 10, 10, 10)
+	.background(Color::rgb(240, 240, 240))
+	.rounded(16.0)
+	.bordered(3)
+	.rounded(16.0);
+
+	snapshots()
+		.await
+		.assert("rounded", &mut widget, PhysicalSize::new(360, 140));
+}
+
+#[tokio::test]
+async fn shadows() {
+	let card = |shadow: BoxShadow| {
+		Rect::new(Color::WHITE)
+			.rounded(12.0)
+			.shadow(shadow.rounded(12.0))
+	};
+	let black = |alpha| Color::rgba(0, 0, 0, alpha);
+
+	let mut widget = Row::new(vec![
+		card(BoxShadow::new(black(160)).offset(4.0, 4.0)),
+		card(
+			BoxShadow::new(black(120))
+				.offset(0.0, 6.0)
+				.blurred(12.0),
+		),
+		card(
+			BoxShadow::new(Color::rgb(200, 40, 40))
+				.blurred(8.0)
+				.spread(4.0),
+		),
+	])
+	.spaced(40)
+	.padded(30, 30, 30, 30)
+	.background(Color::rgb(230, 230, 230));
+
+	snapshots()
+		.await
+		.assert("shadows", &mut widget, PhysicalSize::new(420, 160));
+}
+
+#[tokio::test]
+async fn background() {
+	let mut widget = String::from("bg")
+		.padded(10, 10, 10, 10)
+		.background(Fill::horizontal(
+			Color::rgb(255, 200, 0),
+			Color::rgb(0, 200, 120),
+		));
+
+	snapshots()
+		.await
+		.assert("background", &mut widget, PhysicalSize::new(300, 200));
+}
+
+#[tokio::test]
+async fn colors() {
+	let mut widget = String::from("ok")
+		.text_color(Color::hex("#f0f0f0").unwrap())
+		.padded(5, 5, 5, 5)
+		.background(Color::rgb(20, 120, 40))
+		.bordered(4)
+		.colored(Color::hex("#c02020").unwrap());
+
+	snapshots()
+		.await
+		.assert("colors", &mut widget, PhysicalSize::new(300, 150));
+}
+
+#[tokio::test]
+async fn font_sizes() {
+	let mut widget = Column::new(vec![
+		String::from("small").font_size(24.0),
+		String::from("medium").font_size(40.0),
+		String::from("large").font_size(64.0),
+	]);
+
+	snapshots()
+		.await
+		.assert("font_sizes", &mut widget, PhysicalSize::new(300, 200));
+}
+
+#[tokio::test]
+async fn wrapped_text() {
+	let mut widget = Column::new(vec![
+		String::from("lines are broken between words")
+			.wrapped(Wrap::Word)
+			.bordered(1),
+		String::from("newlines\nbreak lines")
+			.wrapped(Wrap::Word)
+			.bordered(1),
+		String::from("this line is much too long to fit")
+			.wrapped(Wrap::None)
+			.bordered(1),
+		String::from("only two lines of this long text are shown, the rest is left out")
+			.wrapped(Wrap::Word)
+			.max_lines(2)
+			.bordered(1),
+	])
+	.font_size(28.0);
+
+	snapshots()
+		.await
+		.assert("wrapped_text", &mut widget, PhysicalSize::new(240, 400));
+}
+
+#[cfg(feature = "shaping")]
+#[tokio::test]
+async fn font_fallback() {
+	let font = Font::roboto()
+		.unwrap()
+		.with_fallback(Font::new(include_bytes!("../res/DejaVu/DejaVuSans.ttf")).unwrap());
+	let mut widget = Column::new(vec![
+		String::from("Roboto"),
+		String::from("مرحبا بالعالم"),
+		String::from("Hi مرحبا"),
+	])
+	.font_size(40.0);
+
+	snapshots()
+		.await
+		.with_font(font)
+		.assert("font_fallback", &mut widget, PhysicalSize::new(300, 200));
+}
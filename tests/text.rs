@@ -0,0 +1,254 @@
+use std::rc::Rc;
+
+use kitsune_ui::{
+	layout::DEFAULT_FONT_SIZE,
+	text::{Font, Paragraph, Wrap},
+};
+
+fn paragraph(text: &str, width: Option<u32>, wrap: Wrap, max_lines: Option<usize>) -> Paragraph {
+	let font = Font::roboto().unwrap();
+	Paragraph::new(&font, text, DEFAULT_FONT_SIZE, width, wrap, max_lines)
+}
+
+/// The width of `text` on a single line.
+fn width(text: &str) -> u32 {
+	paragraph(text, None, Wrap::Word, None).width()
+}
+
+/// The text on every line of `paragraph`.
+fn lines<'a>(paragraph: &Paragraph, text: &'a str) -> Vec<&'a str> {
+	paragraph
+		.lines()
+		.iter()
+		.map(|x| &text[x.range.clone()])
+		.collect()
+}
+
+/// The clusters of the glyphs of `text`, from left to right.
+fn clusters(text: &str) -> Vec<usize> {
+	Font::roboto()
+		.unwrap()
+		.shape(text, DEFAULT_FONT_SIZE)
+		.iter()
+		.flat_map(|x| &x.glyphs)
+		.map(|x| x.cluster)
+		.collect()
+}
+
+#[test]
+fn paragraphs_break_lines_before_they_overflow() {
+	let font = Font::roboto().unwrap();
+	let width = font
+		.glyph('n', DEFAULT_FONT_SIZE)
+		.advance()
+		* 3.0;
+	let paragraph = paragraph("nnnnnnn", Some(width.round() as u32), Wrap::Word, None);
+
+	let lines = paragraph
+		.lines()
+		.iter()
+		.map(|x| x.range.clone())
+		.collect::<Vec<_>>();
+	assert_eq!(lines, vec![0..3, 3..6, 6..7]);
+	assert_eq!(paragraph.height(), 3 * paragraph.line_height());
+	assert_eq!(paragraph.width(), width.round() as u32);
+}
+
+#[test]
+fn characters_are_placed_after_each_other() {
+	let paragraph = paragraph("text", None, Wrap::Word, None);
+
+	let characters = &paragraph.lines()[0].characters;
+	assert_eq!(characters.len(), 4);
+	assert_eq!(characters[0].0, 0.0);
+	for pair in characters.windows(2) {
+		assert_eq!(pair[0].1, pair[1].0);
+	}
+}
+
+#[test]
+fn pairs_are_kerned() {
+	let font = Font::roboto().unwrap();
+	let (a, v) = (
+		font.glyph('A', DEFAULT_FONT_SIZE),
+		font.glyph('V', DEFAULT_FONT_SIZE),
+	);
+	let kerned = font.shape("AV", DEFAULT_FONT_SIZE)[0].advance();
+
+	assert!(kerned < a.advance() + v.advance());
+}
+
+#[cfg(feature = "shaping")]
+#[test]
+fn ligatures_are_one_glyph_shared_by_their_characters() {
+	let paragraph = paragraph("fi", None, Wrap::Word, None);
+	let line = &paragraph.lines()[0];
+
+	assert_eq!(line.runs[0].glyphs.len(), 1);
+	assert_eq!(line.characters.len(), 2);
+	assert_eq!(line.characters[0].1, line.characters[1].0);
+}
+
+#[cfg(feature = "shaping")]
+#[test]
+fn right_to_left_text_is_reversed() {
+	// Every hebrew letter takes up two bytes.
+	assert_eq!(clusters("אבג"), vec![4, 2, 0]);
+	assert_eq!(clusters("ab אבג"), vec![0, 1, 2, 7, 5, 3]);
+}
+
+#[cfg(feature = "shaping")]
+#[test]
+fn right_to_left_characters_are_placed_from_the_right() {
+	let paragraph = paragraph("אבג", None, Wrap::Word, None);
+
+	let characters = &paragraph.lines()[0].characters;
+	assert!(characters[0].0 > characters[1].0);
+	assert!(characters[1].0 > characters[2].0);
+	assert_eq!(characters[2].0, 0.0);
+}
+
+#[cfg(not(feature = "shaping"))]
+#[test]
+fn every_character_is_a_glyph_without_shaping() {
+	assert_eq!(clusters("fi אב"), vec![0, 1, 2, 3, 5]);
+}
+
+#[test]
+fn paragraphs_are_only_broken_into_lines_again_when_they_change() {
+	let font = Font::roboto().unwrap();
+	let paragraph = |text, width| font.paragraph(text, DEFAULT_FONT_SIZE, width, Wrap::Word, None);
+
+	let first = paragraph("one two", Some(100));
+	assert!(Rc::ptr_eq(&first, &paragraph("one two", Some(100))));
+	assert!(!Rc::ptr_eq(&first, &paragraph("one two", Some(200))));
+	assert!(!Rc::ptr_eq(&first, &paragraph("one three", Some(100))));
+}
+
+#[test]
+fn the_least_recently_used_paragraphs_are_broken_into_lines_again() {
+	let font = Font::roboto().unwrap();
+	let paragraph = |text: &str| font.paragraph(text, DEFAULT_FONT_SIZE, None, Wrap::Word, None);
+
+	let (kept, evicted) = (paragraph("kept"), paragraph("evicted"));
+	for i in 0..1_000 {
+		paragraph(&i.to_string());
+		paragraph("kept");
+	}
+
+	assert!(Rc::ptr_eq(&kept, &paragraph("kept")));
+	assert!(!Rc::ptr_eq(&evicted, &paragraph("evicted")));
+}
+
+/// A font that has arabic letters, which Roboto doesn't.
+fn dejavu() -> Font {
+	Font::new(include_bytes!("../res/DejaVu/DejaVuSans.ttf")).unwrap()
+}
+
+#[test]
+fn characters_the_font_does_not_have_are_drawn_by_its_fallback() {
+	let roboto = Font::roboto().unwrap();
+	let font = Font::roboto()
+		.unwrap()
+		.with_fallback(dejavu());
+
+	let advance = |font: &Font, value| {
+		font.glyph(value, DEFAULT_FONT_SIZE)
+			.advance()
+	};
+	assert_eq!(advance(&font, 'a'), advance(&roboto, 'a'));
+	assert_eq!(advance(&font, 'ش'), advance(&dejavu(), 'ش'));
+	assert_ne!(advance(&font, 'ش'), advance(&roboto, 'ش'));
+}
+
+#[cfg(feature = "shaping")]
+#[test]
+fn text_is_shaped_in_runs_of_the_font_that_draws_it() {
+	let font = Font::roboto()
+		.unwrap()
+		.with_fallback(dejavu());
+
+	// Every arabic letter takes up two bytes.
+	let runs = font.shape("ab سلام", DEFAULT_FONT_SIZE);
+	let runs = runs
+		.iter()
+		.map(|x| (x.range.clone(), x.rtl))
+		.collect::<Vec<_>>();
+	assert_eq!(runs, vec![(0..3, false), (3..11, true)]);
+
+	// Arabic letters are joined, so they are drawn differently than on their own.
+	let joined = &font.shape("سلام", DEFAULT_FONT_SIZE)[0].glyphs;
+	let alone = &font.shape("س", DEFAULT_FONT_SIZE)[0].glyphs;
+	assert_ne!(joined.last().unwrap().advance, alone[0].advance);
+}
+
+#[test]
+fn lines_are_broken_between_words() {
+	let text = "one two three";
+	let paragraph = paragraph(text, Some(width("one two")), Wrap::Word, None);
+
+	assert_eq!(lines(&paragraph, text), vec!["one two ", "three"]);
+}
+
+#[test]
+fn words_that_do_not_fit_are_broken_between_characters() {
+	let text = "a abcdef";
+	let paragraph = paragraph(text, Some(width("abc")), Wrap::Word, None);
+
+	assert_eq!(lines(&paragraph, text), vec!["a ", "abc", "def"]);
+}
+
+#[test]
+fn character_wrapping_ignores_words() {
+	let text = "one two";
+	let paragraph = paragraph(text, Some(width("one t")), Wrap::Character, None);
+
+	assert_eq!(lines(&paragraph, text), vec!["one t", "wo"]);
+}
+
+#[test]
+fn newlines_always_break_lines() {
+	let text = "one\ntwo\r\nthree";
+	let paragraph = paragraph(text, None, Wrap::Word, None);
+
+	assert_eq!(lines(&paragraph, text), vec!["one\n", "two\r\n", "three"]);
+	// Newlines take up no space, but still have a place for every character.
+	assert_eq!(paragraph.width(), width("three"));
+	let line = &paragraph.lines()[1];
+	assert_eq!(line.characters.len(), 5);
+	assert_eq!(line.characters[3].0, line.characters[3].1);
+}
+
+#[test]
+fn lines_that_do_not_fit_end_with_an_ellipsis_without_wrapping() {
+	let text = "one two three";
+	let max = width("one two");
+	let paragraph = paragraph(text, Some(max), Wrap::None, None);
+
+	assert_eq!(paragraph.lines().len(), 1);
+	assert!(paragraph.width() <= max);
+	let font = Font::roboto().unwrap();
+	let ellipsis = font.glyph('\u{2026}', DEFAULT_FONT_SIZE);
+	let line = &paragraph.lines()[0];
+	let last = &line
+		.runs
+		.last()
+		.unwrap()
+		.glyphs
+		.last()
+		.unwrap()
+		.glyph;
+	assert_eq!(font.key(last), font.key(&ellipsis));
+	// The characters that are cut off are still there, without taking up space.
+	assert_eq!(line.characters.len(), text.len());
+}
+
+#[test]
+fn text_after_the_last_line_is_left_out() {
+	let text = "one\ntwo\nthree";
+	let paragraph = paragraph(text, None, Wrap::Word, Some(2));
+
+	assert_eq!(lines(&paragraph, text), vec!["one\n", "two\n"]);
+	// The last line that is shown ends with an ellipsis.
+	assert!(paragraph.lines()[1].width() > width("two") as f32);
+}
@@ -0,0 +1,174 @@
+mod common;
+
+use common::layout;
+use kitsune_ui::{
+	event::{
+		dispatch, ElementState, Event, Ime, Key, Modifiers, MouseButton, PointerEvent, PointerKind,
+	},
+	view::PhysicalPosition,
+	widget::{TextInput, Widget},
+};
+
+const SHIFT: Modifiers = Modifiers {
+	shift: true,
+	ctrl: false,
+	alt: false,
+	logo: false,
+};
+
+const CTRL: Modifiers = Modifiers {
+	shift: false,
+	ctrl: true,
+	alt: false,
+	logo: false,
+};
+
+fn key(key: Key) -> Event {
+	Event::Keyboard {
+		key,
+		state: ElementState::Pressed,
+	}
+}
+
+fn modifiers(input: &mut TextInput, modifiers: Modifiers) {
+	input.handle_key(&Event::ModifiersChanged(modifiers));
+}
+
+fn type_text(input: &mut TextInput, text: &str) {
+	for character in text.chars() {
+		input.handle_key(&Event::Text(character));
+	}
+}
+
+fn press(input: &mut TextInput, keys: &[Key]) {
+	for x in keys {
+		input.handle_key(&key(*x));
+	}
+}
+
+#[test]
+fn typing_inserts_at_the_caret() {
+	let mut input = TextInput::new("");
+
+	type_text(&mut input, "helo");
+	press(&mut input, &[Key::Left]);
+	type_text(&mut input, "l");
+
+	assert_eq!(input.value(), "hello");
+	assert_eq!(input.caret(), 4);
+}
+
+#[test]
+fn control_characters_are_not_inserted() {
+	let mut input = TextInput::new("a");
+
+	type_text(&mut input, "\u{8}\r\u{7f}b");
+
+	assert_eq!(input.value(), "ab");
+}
+
+#[test]
+fn backspace_and_delete_remove_characters_around_the_caret() {
+	let mut input = TextInput::new("añb€c");
+
+	press(&mut input, &[Key::Left, Key::Back]);
+	assert_eq!(input.value(), "añbc");
+
+	press(&mut input, &[Key::Home, Key::Right, Key::Delete]);
+	assert_eq!(input.value(), "abc");
+
+	press(&mut input, &[Key::End, Key::Delete]);
+	assert_eq!(input.value(), "abc");
+	assert_eq!(input.caret(), 3);
+}
+
+#[test]
+fn shift_selects_and_typing_replaces_the_selection() {
+	let mut input = TextInput::new("hello world");
+
+	modifiers(&mut input, SHIFT);
+	press(&mut input, &[Key::Left; 5]);
+	assert_eq!(input.selected_text(), "world");
+
+	modifiers(&mut input, Modifiers::default());
+	type_text(&mut input, "there");
+	assert_eq!(input.value(), "hello there");
+	assert_eq!(input.selection(), None);
+
+	modifiers(&mut input, SHIFT);
+	press(&mut input, &[Key::Home]);
+	modifiers(&mut input, Modifiers::default());
+	press(&mut input, &[Key::Right]);
+	assert_eq!(input.selection(), None);
+	assert_eq!(input.caret(), 11);
+
+	modifiers(&mut input, CTRL);
+	press(&mut input, &[Key::A]);
+	modifiers(&mut input, Modifiers::default());
+	press(&mut input, &[Key::Back]);
+	assert_eq!(input.value(), "");
+}
+
+#[test]
+fn composed_text_is_shown_until_it_is_committed() {
+	let mut input = TextInput::new("ab");
+	press(&mut input, &[Key::Left]);
+
+	let preedit = Ime::Preedit(String::from("にほ"), Some((6, 6)));
+	input.handle_key(&Event::Ime(preedit));
+	assert_eq!(input.value(), "ab");
+	assert_eq!(
+		layout(&input, 400, 100).children()[0]
+			.children()
+			.len(),
+		4
+	);
+
+	input.handle_key(&Event::Ime(Ime::Commit(String::from("日本"))));
+	assert_eq!(input.value(), "a日本b");
+	assert_eq!(input.caret(), 7);
+	assert_eq!(
+		layout(&input, 400, 100).children()[0]
+			.children()
+			.len(),
+		4
+	);
+}
+
+#[test]
+fn clicking_moves_the_caret_and_dragging_selects() {
+	let mut input = TextInput::new("abcd");
+	let layout = layout(&input, 400, 100);
+	let characters = layout.children()[0].children();
+	let at = |index: usize| characters[index].view().offset().x as f64;
+	let y = characters[0].view().height() as f64 / 2.0;
+
+	let button = |state| PointerKind::Button {
+		button: MouseButton::Left,
+		state,
+	};
+	let press = PointerEvent::new(
+		button(ElementState::Pressed),
+		PhysicalPosition::new(at(1) + 2.0, y),
+		None,
+	);
+	dispatch(&mut input, &layout, &press);
+	assert_eq!(input.caret(), 1);
+
+	let drag = PointerEvent::new(
+		PointerKind::Moved,
+		PhysicalPosition::new(at(3) - 2.0, y),
+		None,
+	);
+	dispatch(&mut input, &layout, &drag);
+	assert_eq!(input.selected_text(), "bc");
+
+	let release = PointerEvent::new(
+		button(ElementState::Released),
+		PhysicalPosition::new(at(3), y),
+		None,
+	);
+	dispatch(&mut input, &layout, &release);
+	dispatch(&mut input, &layout, &drag);
+	assert_eq!(input.selected_text(), "bc");
+}